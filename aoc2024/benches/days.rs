@@ -0,0 +1,69 @@
+/*
+ * Benchmarks for every day's parse, part1 and part2, against `data/N.example` and, when it has
+ * been downloaded, `data/N.input`.
+ *
+ *  cargo bench --bench days                              # everything
+ *  cargo bench --bench days -- day6/input                # one day and input
+ *  cargo bench --bench days -- --save-baseline before    # record a baseline
+ *  cargo bench --bench days -- --baseline before         # compare against it after a change
+ *
+ * `--bench days` keeps criterion's options away from the unit test harnesses.
+ */
+use std::hint::black_box;
+use std::path::Path;
+
+use aoc2024::Solution;
+use aoc2024::days::{day1::Day1, day2::Day2, day3::Day3, day4::Day4, day5::Day5, day6::Day6};
+use aoc2024::input::{example_path, input_path};
+use criterion::{BatchSize, Criterion, criterion_group, criterion_main};
+
+fn bench_day<S: Solution>(c: &mut Criterion) {
+    bench_file::<S>(c, "example", &example_path(S::DAY));
+    bench_file::<S>(c, "input", &input_path(S::DAY));
+}
+
+fn bench_file<S: Solution>(c: &mut Criterion, name: &str, path: &Path) {
+    // Real inputs aren't checked in, so skip the ones that haven't been downloaded
+    let Ok(data) = std::fs::read_to_string(path) else {
+        return;
+    };
+    // Each part gets a freshly parsed input (not timed), so nothing cached by an earlier
+    // iteration or by the other part makes it look faster than it is
+    let parse =
+        || S::parse(&data).unwrap_or_else(|e| panic!("{}", e.in_file(path.display().to_string())));
+    parse();
+
+    let mut group = c.benchmark_group(format!("day{}/{name}", S::DAY));
+    if name == "input" {
+        // Some parts take seconds on the real input, keep the run time bearable
+        group.sample_size(10);
+    }
+    group.bench_function("parse", |b| b.iter(|| S::parse(black_box(&data))));
+    group.bench_function("part1", |b| {
+        b.iter_batched(
+            parse,
+            |input| S::part1(black_box(&input)),
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("part2", |b| {
+        b.iter_batched(
+            parse,
+            |input| S::part2(black_box(&input)),
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+}
+
+fn days(c: &mut Criterion) {
+    bench_day::<Day1>(c);
+    bench_day::<Day2>(c);
+    bench_day::<Day3>(c);
+    bench_day::<Day4>(c);
+    bench_day::<Day5>(c);
+    bench_day::<Day6>(c);
+}
+
+criterion_group!(benches, days);
+criterion_main!(benches);
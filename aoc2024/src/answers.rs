@@ -0,0 +1,338 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use crate::Error;
+use crate::input::{data_dir, read_file};
+
+/// Which of a day's data files an answer belongs to
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Source {
+    Example,
+    /// Another example, `data/N.<name>.example`. The name can't be `part1` or `part2`.
+    Extra(String),
+    Input,
+}
+
+impl Source {
+    pub fn path(&self, day: u8) -> PathBuf {
+        data_dir().join(self.file_name(day))
+    }
+
+    /// The file in `data/`
+    pub fn file_name(&self, day: u8) -> String {
+        match self {
+            Source::Example => format!("{day}.example"),
+            Source::Extra(name) => format!("{day}.{name}.example"),
+            Source::Input => format!("{day}.input"),
+        }
+    }
+
+    /// The TOML table this source's answers live in, below the day
+    fn key(&self) -> String {
+        match self {
+            Source::Example => "example".to_string(),
+            Source::Extra(name) => format!("example.{name}"),
+            Source::Input => "input".to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(&self.key())
+    }
+}
+
+/// Known-good answers, keyed by (day, source, part)
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Answers(BTreeMap<(u8, Source, u8), String>);
+
+impl Answers {
+    /// `answers.toml` at the crate root
+    pub fn path() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("answers.toml")
+    }
+
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let text = read_file(path)?;
+        Self::parse(&text).map_err(|e| Error::Config(format!("{}: {e}", path.display())))
+    }
+
+    /// Parse the TOML layout:
+    ///
+    /// ```toml
+    /// [1.example]
+    /// part1 = 11
+    /// part2 = 31
+    ///
+    /// [1.example.small]    # data/1.small.example
+    /// part1 = 2
+    /// ```
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let table = text.parse::<toml::Table>().map_err(|e| e.to_string())?;
+
+        let mut answers = BTreeMap::new();
+        for (day_key, sources) in &table {
+            let day = day_key
+                .parse::<u8>()
+                .map_err(|_| format!("`{day_key}` is not a day number"))?;
+            let sources = sources
+                .as_table()
+                .ok_or_else(|| format!("[{day}] should be a table"))?;
+
+            for (source_key, parts) in sources {
+                let source = match source_key.as_str() {
+                    "example" => Source::Example,
+                    "input" => Source::Input,
+                    _ => return Err(format!("[{day}.{source_key}] should be example or input")),
+                };
+                let parts = parts
+                    .as_table()
+                    .ok_or_else(|| format!("[{day}.{source}] should be a table"))?;
+
+                for (part_key, answer) in parts {
+                    // Tables inside `[N.example]` are the extra examples
+                    if let (Source::Example, toml::Value::Table(extra)) = (&source, answer) {
+                        let extra_source = Source::Extra(part_key.clone());
+                        for (part_key, answer) in extra {
+                            let (part, answer) = part_answer(day, &extra_source, part_key, answer)?;
+                            answers.insert((day, extra_source.clone(), part), answer);
+                        }
+                        continue;
+                    }
+                    let (part, answer) = part_answer(day, &source, part_key, answer)?;
+                    answers.insert((day, source.clone(), part), answer);
+                }
+            }
+        }
+        Ok(Answers(answers))
+    }
+
+    pub fn get(&self, day: u8, source: &Source, part: u8) -> Option<&str> {
+        self.0.get(&(day, source.clone(), part)).map(String::as_str)
+    }
+
+    /// The part to run to check `day`/`source`: the only one with an answer, or `None` for both.
+    /// An example that's only for one part may not work with the other at all.
+    pub fn only_part(&self, day: u8, source: &Source) -> Option<u8> {
+        match [1, 2].map(|part| self.get(day, source, part).is_some()) {
+            [true, false] => Some(1),
+            [false, true] => Some(2),
+            _ => None,
+        }
+    }
+
+    /// Every recorded (day, source), in order
+    pub fn runs(&self) -> Vec<(u8, Source)> {
+        let mut runs: Vec<_> = self
+            .0
+            .keys()
+            .map(|(day, source, _)| (*day, source.clone()))
+            .collect();
+        runs.dedup();
+        runs
+    }
+}
+
+/// Every `N.example` and `N.<name>.example` file in `dir`, in order
+pub fn example_files(dir: &Path) -> std::io::Result<Vec<(u8, Source)>> {
+    let mut found = vec![];
+    for entry in std::fs::read_dir(dir)? {
+        let name = entry?.file_name();
+        let Some(stem) = name.to_str().and_then(|n| n.strip_suffix(".example")) else {
+            continue;
+        };
+        let (day, source) = match stem.split_once('.') {
+            Some((day, extra)) => (day, Source::Extra(extra.to_string())),
+            None => (stem, Source::Example),
+        };
+        if let Ok(day) = day.parse() {
+            found.push((day, source));
+        }
+    }
+    found.sort();
+    Ok(found)
+}
+
+/// `text` (an `answers.toml`) with `parts` recorded for `day`/`source`, keeping everything else as
+/// it is. Answers already there are left alone; an answer that disagrees with one is an error.
+pub fn add(text: &str, day: u8, source: &Source, parts: &[(u8, String)]) -> Result<String, String> {
+    let existing = Answers::parse(text)?;
+    let mut lines = vec![];
+    for (part, answer) in parts {
+        match existing.get(day, source, *part) {
+            Some(known) if known == answer => {}
+            Some(known) => {
+                return Err(format!(
+                    "{day}.{source}.part{part} is already {known}, not {answer}"
+                ));
+            }
+            // Bare only if it reads back the same, so `007` (no leading zeros in TOML) is quoted
+            None if answer.parse::<i64>().map(|n| n.to_string()).as_ref() == Ok(answer) => {
+                lines.push(format!("part{part} = {answer}"))
+            }
+            None => lines.push(format!("part{part} = {answer:?}")),
+        }
+    }
+    if lines.is_empty() {
+        return Ok(text.to_string());
+    }
+
+    // Into the day's table if it already has one, otherwise a new table at the end
+    let header = format!("[{day}.{source}]");
+    let mut all: Vec<String> = text.lines().map(String::from).collect();
+    match all.iter().position(|l| l.trim() == header) {
+        Some(at) => {
+            // After the table's last line, before any blank lines leading up to the next one
+            let next = all[at + 1..]
+                .iter()
+                .position(|l| l.trim_start().starts_with('['))
+                .map_or(all.len(), |n| at + 1 + n);
+            let end = all[..next]
+                .iter()
+                .rposition(|l| !l.trim().is_empty())
+                .unwrap_or(at)
+                + 1;
+            all.splice(end..end, lines);
+        }
+        None => {
+            if all.last().is_some_and(|l| !l.trim().is_empty()) {
+                all.push(String::new());
+            }
+            all.push(header);
+            all.extend(lines);
+        }
+    }
+    Ok(all.join("\n") + "\n")
+}
+
+fn part_answer(
+    day: u8,
+    source: &Source,
+    part_key: &str,
+    answer: &toml::Value,
+) -> Result<(u8, String), String> {
+    let part = match part_key {
+        "part1" => 1,
+        "part2" => 2,
+        _ => {
+            return Err(format!(
+                "{day}.{source}.{part_key} should be part1 or part2"
+            ));
+        }
+    };
+    let answer = match answer {
+        toml::Value::Integer(n) => n.to_string(),
+        toml::Value::String(s) => s.clone(),
+        _ => {
+            return Err(format!(
+                "{day}.{source}.{part_key} should be a number or string"
+            ));
+        }
+    };
+    Ok((part, answer))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::TempDir;
+
+    #[test]
+    fn parse_layout() {
+        let answers = Answers::parse(
+            r#"
+[1.example]
+part1 = 11
+part2 = 31
+
+[1.example.small]
+part1 = 2
+
+[1.input]
+part2 = "abc"
+"#,
+        )
+        .unwrap();
+        let small = Source::Extra("small".to_string());
+        assert_eq!(answers.get(1, &Source::Example, 1), Some("11"));
+        assert_eq!(answers.get(1, &Source::Input, 2), Some("abc"));
+        assert_eq!(answers.get(1, &Source::Input, 1), None);
+        assert_eq!(
+            answers.runs(),
+            vec![(1, Source::Example), (1, small.clone()), (1, Source::Input)]
+        );
+        assert_eq!(answers.get(1, &small, 1), Some("2"));
+        assert_eq!(answers.only_part(1, &Source::Example), None);
+        assert_eq!(answers.only_part(1, &small), Some(1));
+        assert_eq!(answers.only_part(1, &Source::Input), Some(2));
+        assert!(small.path(1).ends_with("data/1.small.example"));
+    }
+
+    #[test]
+    fn finds_example_files() {
+        let dir = TempDir::new("examples");
+        for name in [
+            "2.example",
+            "1.small.example",
+            "1.example",
+            "1.input",
+            "notes.example",
+        ] {
+            std::fs::write(dir.join(name), "").unwrap();
+        }
+        assert_eq!(
+            example_files(&dir).unwrap(),
+            [
+                (1, Source::Example),
+                (1, Source::Extra("small".to_string())),
+                (2, Source::Example)
+            ]
+        );
+    }
+
+    #[test]
+    fn add_answers() {
+        let text = "# answers\n\n[1.example]\npart1 = 11\n";
+        let parts = [(1, "11".to_string()), (2, "31".to_string())];
+        let added = add(text, 1, &Source::Example, &parts).unwrap();
+        assert_eq!(added, "# answers\n\n[1.example]\npart1 = 11\npart2 = 31\n");
+
+        let extra = Source::Extra("second".to_string());
+        let added = add(&added, 1, &extra, &[(2, "abc".to_string())]).unwrap();
+        assert!(added.ends_with("part2 = 31\n\n[1.example.second]\npart2 = \"abc\"\n"));
+        assert_eq!(
+            Answers::parse(&added).unwrap().get(1, &extra, 2),
+            Some("abc")
+        );
+
+        // At the end of the day's table, not the next one
+        let table = "[1.example]\npart1 = 11\n\n[2.example]\npart1 = 2\n";
+        let added = add(table, 1, &Source::Example, &[(2, "31".to_string())]).unwrap();
+        assert_eq!(
+            added,
+            "[1.example]\npart1 = 11\npart2 = 31\n\n[2.example]\npart1 = 2\n"
+        );
+
+        let added = add("", 2, &Source::Example, &[(1, "007".to_string())]).unwrap();
+        assert_eq!(added, "[2.example]\npart1 = \"007\"\n");
+        assert_eq!(
+            Answers::parse(&added).unwrap().get(2, &Source::Example, 1),
+            Some("007")
+        );
+
+        assert!(add(text, 1, &Source::Example, &[(1, "12".to_string())]).is_err());
+    }
+
+    #[test]
+    fn unknown_part() {
+        assert!(Answers::parse("[1.example]\npart3 = 1").is_err());
+    }
+
+    #[test]
+    fn checked_in_file_parses() {
+        let answers = Answers::load(&Answers::path()).unwrap();
+        assert_eq!(answers.get(6, &Source::Example, 2), Some("6"));
+    }
+}
@@ -1,250 +1,109 @@
-/*
- * Part1: Determine the number of unique positions the guard passes through before leaving the map.
- *  The guard follows one direction until it reaches an obstacle then turns right and continues.
- *
- * Part2: How many distinct positions can an obstacle be placed in the map that would cause the
- * guard to be stuck in a loop?
- */
-
-use std::collections::HashSet;
-
-#[derive(Debug, Clone)]
-struct Grid {
-    height: usize, // X
-    width: usize,  // Y
-    data: Vec<Vec<char>>,
-}
-
-impl Grid {
-    fn new(input: &str) -> Self {
-        let mut height = 0;
-        let mut data = Vec::new();
-        for line in input.lines() {
-            height += 1;
-            let row: Vec<char> = line.chars().collect();
-            data.push(row);
-        }
-        Grid {
-            width: data[0].len(),
-            height,
-            data,
-        }
-    }
-
-    fn get(&self, index: &Point) -> Option<char> {
-        let x = index.x as usize;
-        let y = index.y as usize;
-        if x < self.height && y < self.width {
-            // This looks weird
-            Some(self[index])
-        } else {
-            None
-        }
-    }
-
-    fn set(&mut self, index: &Point, c: char) {
-        self.data[index.x as usize][index.y as usize] = c
-    }
-}
-
-impl std::ops::Index<Point> for Grid {
-    type Output = char;
-
-    fn index(&self, index: Point) -> &Self::Output {
-        &self.data[index.x as usize][index.y as usize]
-    }
-}
-
-impl std::ops::Index<&Point> for Grid {
-    type Output = char;
-
-    fn index(&self, index: &Point) -> &Self::Output {
-        &self.data[index.x as usize][index.y as usize]
-    }
-}
-
-#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
-struct Point {
-    x: i32,
-    y: i32,
-}
-
-impl Point {
-    fn new(x: usize, y: usize) -> Self {
-        Point {
-            x: x as i32,
-            y: y as i32,
-        }
-    }
+use std::fs::File;
+use std::io::BufWriter;
+use std::num::{NonZeroU32, NonZeroUsize};
+use std::path::PathBuf;
+
+use aoc2024::days::day6::Day6;
+use aoc2024::input::InputArgs;
+use aoc2024::picture::{self, Gif};
+use aoc2024::{Solution, exit_with, parallel, terminal};
+use clap::Parser;
+
+#[derive(Parser)]
+struct Args {
+    #[command(flatten)]
+    input: InputArgs,
+
+    /// Threads for the part2 obstacle search, defaults to the number of CPUs
+    #[arg(long, value_name = "N")]
+    threads: Option<NonZeroUsize>,
+
+    /// List every obstacle that causes a loop, with the loop's length and where it's entered
+    #[arg(long)]
+    loops: bool,
+
+    /// Print the map with each loop-causing obstacle drawn as `O`
+    #[arg(long)]
+    overlay: bool,
+
+    /// Animate the guard's walk in the terminal instead of solving
+    #[arg(long)]
+    visualize: bool,
+
+    /// Frame rate for --visualize (changed with + and - while it plays) and --gif
+    #[arg(long, value_name = "N", default_value = "20")]
+    fps: NonZeroU32,
+
+    /// Save the map as a PNG: the guard's trail, and the loop-causing obstacles in red
+    #[arg(long, value_name = "PATH")]
+    png: Option<PathBuf>,
+
+    /// Save the guard's walk as an animated GIF, one frame per step
+    #[arg(long, value_name = "PATH")]
+    gif: Option<PathBuf>,
+
+    /// Pixels per cell in the PNG and GIF
+    #[arg(long, value_name = "N", default_value = "4")]
+    scale: NonZeroUsize,
 }
 
-impl std::ops::Add<Direction> for Point {
-    type Output = Point;
-
-    fn add(self, rhs: Direction) -> Self::Output {
-        match rhs {
-            Direction::North => Point {
-                x: self.x - 1,
-                y: self.y,
-            },
-            Direction::South => Point {
-                x: self.x + 1,
-                y: self.y,
-            },
-            Direction::East => Point {
-                x: self.x,
-                y: self.y + 1,
-            },
-            Direction::West => Point {
-                x: self.x,
-                y: self.y - 1,
-            },
-        }
-    }
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-enum Direction {
-    North,
-    South,
-    East,
-    West,
-}
-
-impl Direction {
-    fn turn(&self) -> Self {
-        match self {
-            Direction::North => Direction::East,
-            Direction::East => Direction::South,
-            Direction::South => Direction::West,
-            Direction::West => Direction::North,
-        }
+fn main() {
+    let args = Args::parse();
+    let input = args.input.load::<Day6>().unwrap_or_else(|e| exit_with(e));
+    if args.visualize {
+        let steps = input.route().len();
+        terminal::play(steps, args.fps, |step, rows, columns| {
+            input.frame(step, rows, columns)
+        })
+        .unwrap_or_else(|e| exit_with(e));
+        return;
     }
-}
 
-fn main() {
-    let data = std::fs::read_to_string("./data/6.example").expect("couldn't open the file");
-    let part1 = part1(&data);
+    let threads = args.threads.unwrap_or_else(parallel::available);
+    let part1 = Day6::part1(&input);
     println!("Part1: {part1}");
-    let part2 = part2(&data);
+    let part2 = input.count_loops(threads);
     println!("Part2: {part2}");
-}
-
-fn part1(data: &str) -> usize {
-    let grid = Grid::new(data);
-
-    get_visited(&grid, false).unwrap().len()
-}
-
-// Damn this is really slow. 20+ seconds!
-fn part2(data: &str) -> usize {
-    let grid = Grid::new(data);
 
-    // Grab visited points
-    let visited = get_visited(&grid, false).unwrap();
-
-    // Get all possible positions
-    let mut product = vec![];
-    for i in 0..grid.width {
-        for j in 0..grid.height {
-            product.push((i, j));
-        }
-    }
-
-    // For each possible position, if it is a wall, check if there is a loop
-    // If there is a loop, filter that as possible position, then count it
-    product
-        .iter()
-        .filter(|&(x, y)| {
-            let p = Point::new(*x, *y);
-            if visited.get(&p) == Some(&p) {
-                let mut new_grid = grid.clone();
-                new_grid.set(&p, '#');
-                get_visited(&new_grid, true).is_none()
-            } else {
-                false
+    if let Some(path) = &args.gif {
+        let write = || {
+            let file = BufWriter::new(File::create(path)?);
+            let (height, width) = (input.grid.height(), input.grid.width());
+            let mut gif = Gif::new(file, height, width, args.scale, args.fps)?;
+            for frame in input.trace() {
+                gif.frame(&frame)?;
             }
-        })
-        .count()
-}
-
-fn get_visited(grid: &Grid, check_loop: bool) -> Option<HashSet<Point>> {
-    let mut start = Point { x: 0, y: 0 };
-
-    for x in 0..grid.width {
-        for y in 0..grid.height {
-            let p = Point::new(x, y);
-            if grid[&p] == '^' {
-                start = p;
-            }
-        }
+            gif.finish().map(drop)
+        };
+        write().unwrap_or_else(|e| exit_with(format!("{}: {e}", path.display())));
     }
 
-    let mut direction = Direction::North;
-    let mut current_position = start;
-
-    let mut next_position = start + direction;
-    let mut visited: HashSet<Point> = std::collections::HashSet::new();
-    let mut seen: HashSet<(Point, Direction)> = std::collections::HashSet::new();
-
-    while grid.get(&current_position).is_some() {
-        visited.insert(current_position);
-        if grid.get(&next_position).is_none() {
-            break;
-        }
-        if grid[next_position] == '#' {
-            direction = direction.turn();
-        } else {
-            current_position = next_position;
-        }
-        if check_loop {
-            // If we have seen the same position and going the same direction, we are in a loop
-            if seen.contains(&(current_position, direction)) {
-                return None;
-            }
-            seen.insert((current_position, direction));
+    if !(args.loops || args.overlay || args.png.is_some()) {
+        return;
+    }
+    let loops = input.loops(threads);
+    if args.loops {
+        println!();
+        for l in &loops {
+            let (entry, heading) = l.entry;
+            println!(
+                "obstacle at {},{}: loop of {} steps, entered at {},{} heading {heading:?}",
+                l.obstacle.x, l.obstacle.y, l.length, entry.x, entry.y
+            );
         }
-        next_position = current_position + direction;
     }
-
-    Some(visited)
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn part1_example() {
-        let data = r"....#.....
-.........#
-..........
-..#.......
-.......#..
-..........
-.#..^.....
-........#.
-#.........
-......#...";
-
-        let result = part1(data);
-        assert_eq!(result, 41);
+    if args.overlay {
+        let mut map = input.grid.clone();
+        for l in &loops {
+            map.set(l.obstacle, 'O');
+        }
+        print!("\n{map}");
     }
-
-    #[test]
-    fn part2_example() {
-        let data = r"....#.....
-.........#
-..........
-..#.......
-.......#..
-..........
-.#..^.....
-........#.
-#.........
-......#...";
-
-        let result = part2(data);
-        assert_eq!(result, 6);
+    if let Some(path) = &args.png {
+        File::create(path)
+            .and_then(|file| {
+                picture::write_png(BufWriter::new(file), &input.picture(&loops), args.scale)
+            })
+            .unwrap_or_else(|e| exit_with(format!("{}: {e}", path.display())));
     }
 }
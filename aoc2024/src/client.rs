@@ -0,0 +1,288 @@
+/*
+ * Talking to adventofcode.com: downloading puzzle inputs into `data/` and posting answers.
+ *
+ * The session token is the `session` cookie from a logged-in browser. It comes from `AOC_SESSION`
+ * or from a config file (`AOC_CONFIG`, default `~/.config/aoc/config.toml`):
+ *
+ *  session = "53616c7465645f5f..."
+ *  base_url = "https://adventofcode.com"   # optional, e.g. a local stub server
+ *
+ * Environment variables win over the config file. `AOC_BASE_URL` overrides the base URL.
+ */
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::Error;
+use crate::input::read_file;
+
+pub const YEAR: u16 = 2024;
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+// Advent of Code asks automated tools to say who they are
+const USER_AGENT: &str = "github.com/jovire/advent-of-code (aoc2024 runner)";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    pub session: Option<String>,
+    pub base_url: String,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            session: None,
+            base_url: DEFAULT_BASE_URL.to_string(),
+        }
+    }
+}
+
+impl Config {
+    /// The config file, then the environment on top of it
+    pub fn load() -> Result<Self, Error> {
+        let mut config = match config_path() {
+            Some(path) if path.exists() => Self::from_file(&path)?,
+            _ => Config::default(),
+        };
+        if let Ok(session) = std::env::var("AOC_SESSION") {
+            config.session = Some(session);
+        }
+        if let Ok(base_url) = std::env::var("AOC_BASE_URL") {
+            config.base_url = base_url;
+        }
+        Ok(config)
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self, Error> {
+        let text = read_file(path)?;
+        let table = text
+            .parse::<toml::Table>()
+            .map_err(|e| Error::Config(format!("{}: {e}", path.display())))?;
+
+        let field = |key: &str| match table.get(key) {
+            None => Ok(None),
+            Some(toml::Value::String(s)) => Ok(Some(s.trim().to_string())),
+            Some(_) => Err(Error::Config(format!(
+                "{}: `{key}` should be a string",
+                path.display()
+            ))),
+        };
+        Ok(Config {
+            session: field("session")?,
+            base_url: field("base_url")?.unwrap_or_else(|| DEFAULT_BASE_URL.to_string()),
+        })
+    }
+}
+
+fn config_path() -> Option<PathBuf> {
+    if let Ok(path) = std::env::var("AOC_CONFIG") {
+        return Some(PathBuf::from(path));
+    }
+    let home = std::env::var("HOME")
+        .or_else(|_| std::env::var("USERPROFILE"))
+        .ok()?;
+    Some(Path::new(&home).join(".config/aoc/config.toml"))
+}
+
+pub struct Client {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+}
+
+impl Client {
+    pub fn new(config: &Config) -> Result<Self, Error> {
+        let session = config.session.clone().ok_or_else(|| {
+            Error::Config(
+                "no session token, set AOC_SESSION or `session` in ~/.config/aoc/config.toml"
+                    .to_string(),
+            )
+        })?;
+        let agent = ureq::Agent::config_builder()
+            .http_status_as_error(false)
+            .timeout_global(Some(Duration::from_secs(30)))
+            .user_agent(USER_AGENT)
+            .build()
+            .into();
+
+        Ok(Client {
+            agent,
+            base_url: config.base_url.trim_end_matches('/').to_string(),
+            session,
+        })
+    }
+
+    fn url(&self, day: u8, rest: &str) -> String {
+        format!("{}/{YEAR}/day/{day}{rest}", self.base_url)
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    /// The puzzle input for `day`, exactly as served
+    pub fn input(&self, day: u8) -> Result<String, Error> {
+        let url = self.url(day, "/input");
+        let response = self.agent.get(&url).header("Cookie", &self.cookie()).call();
+        self.body(day, &url, response)
+    }
+
+    /// Post `answer` for `day`/`part`, returning the page that comes back with the verdict
+    pub fn submit(&self, day: u8, part: u8, answer: &str) -> Result<String, Error> {
+        let url = self.url(day, "/answer");
+        let level = part.to_string();
+        let response = self
+            .agent
+            .post(&url)
+            .header("Cookie", &self.cookie())
+            .send_form([("level", level.as_str()), ("answer", answer)]);
+        self.body(day, &url, response)
+    }
+
+    // The body of a successful response, or what went wrong in words
+    fn body(
+        &self,
+        day: u8,
+        url: &str,
+        response: Result<ureq::http::Response<ureq::Body>, ureq::Error>,
+    ) -> Result<String, Error> {
+        let mut response = response.map_err(|e| Error::Http(format!("{url}: {e}")))?;
+        let status = response.status().as_u16();
+        let body = response
+            .body_mut()
+            .read_to_string()
+            .map_err(|e| Error::Http(format!("{url}: {e}")))?;
+
+        match status {
+            200 => Ok(body),
+            404 => Err(Error::Http(format!("day {day} isn't unlocked yet ({url})"))),
+            400 | 500 if body.contains("log in") => Err(Error::Http(
+                "the session token was rejected, it has probably expired".to_string(),
+            )),
+            _ => Err(Error::Http(format!("{url}: {status} {}", body.trim()))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fetched {
+    /// Already on disk, nothing was downloaded
+    Cached,
+    Downloaded,
+}
+
+/// Make sure `path` holds the input for `day`, downloading it only if it isn't there yet
+pub fn fetch_input(config: &Config, day: u8, path: &Path) -> Result<Fetched, Error> {
+    if path.exists() {
+        return Ok(Fetched::Cached);
+    }
+
+    let input = Client::new(config)?.input(day)?;
+
+    // Write next to the target and rename, so an interrupted download never looks cached
+    let partial = path.with_extension("input.partial");
+    std::fs::write(&partial, input)?;
+    std::fs::rename(&partial, path)?;
+    Ok(Fetched::Downloaded)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread::JoinHandle;
+
+    use super::*;
+    use crate::tests::TempDir;
+
+    /// A local HTTP server answering each request with the next `(status, body)`, in order.
+    /// Joining the handle gives back the raw requests it received.
+    pub(crate) fn stub_server(
+        responses: Vec<(u16, &'static str)>,
+    ) -> (Config, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let config = Config {
+            session: Some("test-session".to_string()),
+            base_url: format!("http://{}", listener.local_addr().unwrap()),
+        };
+
+        let handle = std::thread::spawn(move || {
+            let mut requests = vec![];
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+
+                let mut request = String::new();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(len) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                        content_length = len.trim().parse().unwrap();
+                    }
+                    request.push_str(&line);
+                    if line == "\r\n" || line.is_empty() {
+                        break;
+                    }
+                }
+                let mut content = vec![0; content_length];
+                reader.read_exact(&mut content).unwrap();
+                request.push_str(&String::from_utf8(content).unwrap());
+                requests.push(request);
+
+                let response = format!(
+                    "HTTP/1.1 {status} Stub\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                reader.get_mut().write_all(response.as_bytes()).unwrap();
+            }
+            requests
+        });
+        (config, handle)
+    }
+
+    #[test]
+    fn downloads_and_caches() {
+        let (config, server) = stub_server(vec![(200, "3   4\n4   3\n")]);
+        let dir = TempDir::new("fetch");
+        let path = dir.join("1.input");
+
+        assert_eq!(fetch_input(&config, 1, &path).unwrap(), Fetched::Downloaded);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "3   4\n4   3\n");
+        // The stub only answers once, so a second download would hang or fail
+        assert_eq!(fetch_input(&config, 1, &path).unwrap(), Fetched::Cached);
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /2024/day/1/input "));
+        assert!(requests[0].contains("session=test-session"));
+    }
+
+    #[test]
+    fn not_unlocked() {
+        let (config, server) = stub_server(vec![(404, "Not Found")]);
+        let dir = TempDir::new("locked");
+        let path = dir.join("25.input");
+
+        let err = fetch_input(&config, 25, &path).unwrap_err();
+        assert!(err.to_string().contains("isn't unlocked"));
+        assert!(!path.exists());
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn missing_session() {
+        let config = Config::default();
+        let dir = TempDir::new("nosession");
+        let err = fetch_input(&config, 1, &dir.join("1.input")).unwrap_err();
+        assert!(err.to_string().contains("AOC_SESSION"));
+    }
+
+    #[test]
+    fn config_file() {
+        let dir = TempDir::new("config");
+        let path = dir.join("config.toml");
+        std::fs::write(&path, "session = \"abc\"\n").unwrap();
+        let config = Config::from_file(&path).unwrap();
+        assert_eq!(config.session.as_deref(), Some("abc"));
+        assert_eq!(config.base_url, DEFAULT_BASE_URL);
+    }
+}
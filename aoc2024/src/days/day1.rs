@@ -0,0 +1,89 @@
+/*
+ * Part 1: Find total distance between left and right list. Pair the smallest number together in
+ * both lists and find the difference between them. Do the same for rest of the numbers. Add those
+ * distances.
+ *
+ * Part 2: Determine the similarity score but multiplying the left list by the number of times it
+ * appears in the right list. Add those scores together.
+ */
+use crate::Solution;
+use crate::error::{ParseError, parse_number};
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+
+    // Left and right lists
+    type Input = (Vec<u64>, Vec<u64>);
+    type Answer = u64;
+
+    fn parse(data: &str) -> Result<Self::Input, ParseError> {
+        let mut left = Vec::new();
+        let mut right = Vec::new();
+
+        for line in data.lines() {
+            let mut columns = line.split_whitespace();
+            let (Some(a), Some(b)) = (columns.next(), columns.next()) else {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                return Err(ParseError::at(data, line, "expected two numbers"));
+            };
+            if let Some(extra) = columns.next() {
+                return Err(ParseError::at(data, extra, "expected only two numbers"));
+            }
+            left.push(parse_number(data, a)?);
+            right.push(parse_number(data, b)?);
+        }
+
+        if left.is_empty() {
+            return Err(ParseError::empty());
+        }
+        Ok((left, right))
+    }
+
+    fn part1((left, right): &Self::Input) -> u64 {
+        let mut left = left.clone();
+        let mut right = right.clone();
+
+        left.sort();
+        right.sort();
+
+        left.into_iter()
+            .zip(right)
+            .map(|(v1, v2)| v1.abs_diff(v2))
+            .sum::<u64>()
+    }
+
+    fn part2((left, right): &Self::Input) -> u64 {
+        let mut map = std::collections::HashMap::new();
+        for b in right {
+            *map.entry(*b).or_insert(0) += 1;
+        }
+
+        let mut result = 0;
+        for val in left {
+            if map.contains_key(val) {
+                result += val * map[val];
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bad_number() {
+        let err = Day1::parse("3   4\n4   x\n").unwrap_err();
+        assert_eq!((err.line, err.column, err.token.as_str()), (2, 5, "x"));
+    }
+
+    #[test]
+    fn empty_input() {
+        assert_eq!(Day1::parse("\n").unwrap_err(), ParseError::empty());
+    }
+}
@@ -0,0 +1,90 @@
+/*
+ * Part1: Determine which levels are safe.
+ * Safe is defined as: all increasing OR all decreasing AND adjacent levels are [1,2,3] apart.
+ * Part2: Determine if any levels are safe IF one of the values is removed from the level.
+ */
+use crate::Solution;
+use crate::error::{ParseError, parse_number};
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+
+    type Input = Vec<Vec<i64>>;
+    type Answer = i64;
+
+    fn parse(data: &str) -> Result<Self::Input, ParseError> {
+        let levels = data
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                line.split_whitespace()
+                    .map(|n| parse_number::<i64>(data, n))
+                    .collect::<Result<Vec<i64>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if levels.is_empty() {
+            return Err(ParseError::empty());
+        }
+        Ok(levels)
+    }
+
+    fn part1(levels: &Self::Input) -> i64 {
+        let mut count = 0;
+        for l in levels {
+            if validate_level(l) {
+                count += 1;
+            }
+        }
+        count
+    }
+
+    fn part2(levels: &Self::Input) -> i64 {
+        let mut count = 0;
+        for l in levels {
+            if validate_level(l)
+                || l.iter().enumerate().any(|(i, _)| {
+                    let mut new_level = l.to_vec();
+                    new_level.remove(i);
+                    validate_level(&new_level)
+                })
+            {
+                count += 1;
+            }
+        }
+        count
+    }
+}
+
+fn validate_level(level: &[i64]) -> bool {
+    // They must be sorted (forwards or backwards)
+    // difference between adjacent values cannot be >3
+    (level.is_sorted() || level.iter().rev().is_sorted())
+        && level
+            .windows(2)
+            .all(|w| w[0] != w[1] && (w[0] - w[1]).abs() <= 3)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid() {
+        let valid = vec![7, 6, 4, 2, 1];
+        assert!(validate_level(&valid));
+    }
+    #[test]
+    fn invalid() {
+        let invalid = vec![1, 2, 7, 8, 9];
+        assert!(!validate_level(&invalid));
+    }
+
+    #[test]
+    fn bad_level() {
+        let err = Day2::parse("7 6 4 2 1\n1 2 7 - 9").unwrap_err();
+        assert_eq!((err.line, err.column, err.token.as_str()), (2, 7, "-"));
+    }
+}
@@ -0,0 +1,92 @@
+use regex::Regex;
+
+use crate::Solution;
+use crate::error::{ParseError, parse_number};
+
+/*
+ * Part1: Follow the correct multiplier instruction, mul(x,y), and ignore the rest. Multiply the
+ * values together and then add them to get the final result
+ *
+ * Part2: Same as part1, except if the instruction says `don't` then don't do anything
+ */
+pub struct Day3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    Mul(u64, u64),
+    Do,
+    Dont,
+}
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+
+    type Input = Vec<Instruction>;
+    type Answer = u64;
+
+    fn parse(data: &str) -> Result<Self::Input, ParseError> {
+        if data.trim().is_empty() {
+            return Err(ParseError::empty());
+        }
+        let re = Regex::new(r"mul\((\d{1,3}),(\d{1,3})\)|do\(\)|don't\(\)").unwrap();
+
+        re.captures_iter(data)
+            .map(|cap| match &cap[0] {
+                "do()" => Ok(Instruction::Do),
+                "don't()" => Ok(Instruction::Dont),
+                _ => Ok(Instruction::Mul(
+                    parse_number(data, cap.get(1).unwrap().as_str())?,
+                    parse_number(data, cap.get(2).unwrap().as_str())?,
+                )),
+            })
+            .collect()
+    }
+
+    fn part1(instructions: &Self::Input) -> u64 {
+        instructions
+            .iter()
+            .map(|i| match i {
+                Instruction::Mul(a, b) => a * b,
+                _ => 0,
+            })
+            .sum::<u64>()
+    }
+
+    fn part2(instructions: &Self::Input) -> u64 {
+        let mut sum = true;
+        let mut result = vec![];
+        for instruction in instructions {
+            match instruction {
+                Instruction::Do => sum = true,
+                Instruction::Dont => sum = false,
+                Instruction::Mul(a, b) => {
+                    if sum {
+                        result.push(a * b);
+                    }
+                }
+            }
+        }
+        result.iter().sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_example() {
+        let data = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+
+        let result = Day3::part1(&Day3::parse(data).unwrap());
+        assert_eq!(result, 161);
+    }
+
+    #[test]
+    fn part2_example() {
+        let data = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+
+        let result = Day3::part2(&Day3::parse(data).unwrap());
+        assert_eq!(result, 48);
+    }
+}
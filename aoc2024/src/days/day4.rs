@@ -0,0 +1,192 @@
+/*
+ * Part1: Word Search. Find the number of times 'XMAS' appears. It can be forwards, backwards, or
+ * diagonal.
+ *
+ * Part2: Actually, you are supposed to find 'MAS' in the shape of an X. Each X is one. Find total
+ * number of Xs
+ *
+ * Part1 is a `wordsearch` for XMAS. Part2 is the `pattern` below in any orientation; `crosses`
+ * finds the same Xs as pairs of MAS words instead, to say which way each one reads.
+ */
+use crate::Solution;
+use crate::error::ParseError;
+use crate::geometry::{Direction, Point};
+use crate::grid::Grid;
+use crate::pattern::{Matcher, Pattern};
+use crate::picture::{self, Rgb};
+use crate::svg::{self, Line};
+use crate::wordsearch::{Match, WordSearch};
+
+pub struct Day4;
+
+const X_MAS: &str = "\
+M.S
+.A.
+M.S
+";
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+
+    type Input = Grid<char>;
+    type Answer = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Grid::parse(input, Some)
+    }
+
+    fn part1(grid: &Self::Input) -> u32 {
+        xmas(grid).count() as u32
+    }
+
+    fn part2(grid: &Self::Input) -> u32 {
+        let cross = Pattern::parse(X_MAS, '.').expect("the pattern parses");
+        Matcher::symmetric(cross).count(grid) as u32
+    }
+}
+
+/// Two MAS on the diagonals through the same `A`, each read from M to S
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cross {
+    pub centre: Point,
+    pub arms: [Match; 2],
+}
+
+/// Every XMAS, in the order their `X`s appear
+pub fn xmas(grid: &Grid<char>) -> impl Iterator<Item = Match> {
+    WordSearch::new(["XMAS"])
+        .find(grid, &Direction::ALL)
+        .into_iter()
+        .map(|found| found.location)
+}
+
+/// Every MAS cross, in the order their `A`s appear
+pub fn crosses(grid: &Grid<char>) -> impl Iterator<Item = Cross> {
+    // Only one MAS can go each way through an `A`, so two diagonal ones make a cross
+    let mut arms: Vec<Match> = WordSearch::new(["MAS"])
+        .find(grid, &Direction::DIAGONAL)
+        .into_iter()
+        .map(|found| found.location)
+        .collect();
+    let centre = |arm: &Match| arm.start + arm.direction;
+    arms.sort_by_key(centre);
+    arms.chunk_by(|a, b| centre(a) == centre(b))
+        .filter_map(|arms| match *arms {
+            [a, b] => Some(Cross {
+                centre: centre(&a),
+                arms: [a, b],
+            }),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .into_iter()
+}
+
+const XMAS: Rgb = [70, 130, 230];
+const CROSS: Rgb = [240, 150, 40];
+const BOTH: Rgb = [250, 240, 120];
+
+/// The word search with every letter of an XMAS in blue, every letter of a MAS cross in orange
+/// and letters in both in yellow. Other letters are shades of grey.
+pub fn picture(grid: &Grid<char>) -> Grid<Rgb> {
+    let mut image = picture::paint(grid, |_, c| {
+        let shade = match c {
+            'X' => 40,
+            'M' => 60,
+            'A' => 80,
+            'S' => 100,
+            _ => 20,
+        };
+        [shade; 3]
+    });
+    for word in xmas(grid) {
+        for p in word.cells() {
+            image.set(p, XMAS);
+        }
+    }
+    for cross in crosses(grid) {
+        for p in cross.arms.iter().flat_map(Match::cells) {
+            let colour = match image.get(p) {
+                Some(&XMAS) | Some(&BOTH) => BOTH,
+                _ => CROSS,
+            };
+            image.set(p, colour);
+        }
+    }
+    image
+}
+
+/// The word search as an SVG, with a line through each XMAS and a pair of crossed lines through
+/// each MAS cross
+pub fn svg(grid: &Grid<char>) -> String {
+    let words = xmas(grid).map(|word| Line {
+        from: word.start,
+        to: word.end(),
+        colour: "#4682e6",
+    });
+    let crosses = crosses(grid).flat_map(|cross| {
+        cross.arms.map(|arm| Line {
+            from: arm.start,
+            to: arm.end(),
+            colour: "#f09628",
+        })
+    });
+    svg::letters(grid, &words.chain(crosses).collect::<Vec<_>>())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ragged_rows() {
+        let err = Day4::parse("XMAS\nXMA\nXMAS").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+    }
+
+    #[test]
+    fn highlights_matches() {
+        let grid = Day4::parse("XMAS.\n.M.S.\n..A..\n.M.S.\n").unwrap();
+        let image = picture(&grid);
+        let at = |x, y| *image.get(Point::new(x, y)).unwrap();
+        // XMAS along the top and down the diagonal, which is also one arm of the cross
+        assert_eq!(at(0, 3), XMAS);
+        assert_eq!(at(2, 2), BOTH);
+        assert_eq!(at(1, 3), CROSS);
+        assert_eq!(at(0, 4), [20; 3]);
+    }
+
+    #[test]
+    fn match_locations() {
+        let grid = Day4::parse("XMAS\nMM..\nA.A.\nS..S\n").unwrap();
+        let found: Vec<_> = xmas(&grid).map(|m| (m.direction, m.end())).collect();
+        assert_eq!(
+            found,
+            [
+                (Direction::East, Point::new(0, 3)),
+                (Direction::SouthEast, Point::new(3, 3)),
+                (Direction::South, Point::new(3, 0)),
+            ]
+        );
+
+        // Every match on the example reads what it should
+        let example = Day4::parse(include_str!("../../data/4.example")).unwrap();
+        let read = |m: Match| -> String { m.cells().map(|p| example.get(p).unwrap()).collect() };
+        assert!(xmas(&example).all(|m| read(m) == "XMAS"));
+        let crosses: Vec<_> = crosses(&example).collect();
+        assert_eq!(crosses.len(), 9);
+        assert_eq!(Day4::part2(&example), 9);
+        for cross in &crosses {
+            assert!(cross.arms.iter().all(|&arm| read(arm) == "MAS"));
+            assert!(
+                cross
+                    .arms
+                    .iter()
+                    .all(|arm| arm.cells().nth(1) == Some(cross.centre))
+            );
+        }
+
+        // A line for each XMAS and two for each cross
+        assert_eq!(svg(&example).matches("<line").count(), 18 + 2 * 9);
+    }
+}
@@ -0,0 +1,125 @@
+use std::collections::BTreeMap;
+
+use crate::Solution;
+use crate::error::{ParseError, parse_number};
+
+/*
+ * Part1: Given a set of rules, is the update valid? For the valid updates, sum the mid points
+ * Part2: Correct the incorrectly-ordered updates, and perform the same calculation as Part1
+ */
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+
+    // Rules (page -> pages that may follow it) and updates
+    type Input = (BTreeMap<u32, Vec<u32>>, Vec<Vec<u32>>);
+    type Answer = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let mut rules_map: BTreeMap<u32, Vec<u32>> = BTreeMap::new();
+        let mut updates: Vec<Vec<u32>> = Vec::new();
+
+        for line in input.lines() {
+            if line.is_empty() {
+                continue;
+            }
+            if let Some((k, v)) = line.split_once('|') {
+                let k = parse_number::<u32>(input, k)?;
+                let v = parse_number::<u32>(input, v)?;
+                let entry = rules_map.entry(k).or_default();
+                entry.push(v);
+            } else {
+                let update = line
+                    .split(',')
+                    .map(|n| parse_number::<u32>(input, n))
+                    .collect::<Result<_, _>>()?;
+                updates.push(update);
+            }
+        }
+
+        if rules_map.is_empty() && updates.is_empty() {
+            return Err(ParseError::empty());
+        }
+        Ok((rules_map, updates))
+    }
+
+    fn part1((rules, updates): &Self::Input) -> u32 {
+        updates
+            .iter()
+            .filter(|u| validate_rule(rules, u))
+            .map(|x| x[x.len() / 2])
+            .sum::<u32>()
+    }
+
+    fn part2((rules, updates): &Self::Input) -> u32 {
+        // Filter for incorrect updates. Correct them and sum the midpoints
+        updates
+            .iter()
+            .filter(|u| !validate_rule(rules, u))
+            .map(|v| {
+                let mut v = v.clone();
+                v.sort_by(|&a, &b| {
+                    if validate_rule(rules, &[a, b]) {
+                        std::cmp::Ordering::Less
+                    } else {
+                        std::cmp::Ordering::Greater
+                    }
+                });
+                v
+            })
+            .map(|x| x[x.len() / 2])
+            .sum::<u32>()
+    }
+}
+
+fn validate_rule(rules: &BTreeMap<u32, Vec<u32>>, update: &[u32]) -> bool {
+    // For each item in the update, check if the following elements are also valid
+
+    let update_len = update.len();
+    for i in 0..update_len - 1 {
+        let curr_val = update[i];
+        if !rules.contains_key(&curr_val) {
+            return false;
+        }
+        for val in &update[i + 1..] {
+            // Is current value valid?
+            if !rules[&curr_val].contains(val) {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_passing_rule() {
+        let (rules, _) = Day5::parse(include_str!("../../data/5.example")).unwrap();
+
+        let valid_update = vec![75, 47, 61, 53, 29];
+        let result = validate_rule(&rules, &valid_update);
+        assert!(result);
+
+        let valid_update2 = vec![97, 61, 53, 29, 13];
+        let result = validate_rule(&rules, &valid_update2);
+        assert!(result);
+
+        let valid_update3 = vec![75, 29, 13];
+        let result = validate_rule(&rules, &valid_update3);
+        assert!(result);
+
+        let invalid_update = vec![75, 97, 47, 61, 53];
+        let result = validate_rule(&rules, &invalid_update);
+        assert!(!result);
+    }
+
+    #[test]
+    fn bad_rule() {
+        let err = Day5::parse("47|53\n97|\n\n75,47").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 4));
+    }
+}
@@ -0,0 +1,635 @@
+/*
+ * Part1: Determine the number of unique positions the guard passes through before leaving the map.
+ *  The guard follows one direction until it reaches an obstacle then turns right and continues.
+ *
+ * Part2: How many distinct positions can an obstacle be placed in the map that would cause the
+ * guard to be stuck in a loop?
+ *
+ * Part2 used to clone the map for every candidate and walk it from the start one cell at a time,
+ * which took 20+ seconds. Now each candidate is tried from the step before the guard first
+ * reaches it, the guard jumps from obstacle to obstacle using precomputed tables, and the states
+ * seen go in a bitset instead of a hash set. The candidates don't depend on each other, so they're
+ * also spread over threads.
+ */
+
+use std::num::NonZeroUsize;
+use std::sync::OnceLock;
+
+use crossterm::style::Stylize;
+
+use crate::Solution;
+use crate::error::ParseError;
+use crate::geometry::{Direction, Point};
+use crate::grid::Grid;
+use crate::picture::{self, Rgb};
+use crate::{parallel, terminal};
+
+pub struct Day6;
+
+#[derive(Debug)]
+pub struct Map {
+    pub grid: Grid<char>,
+    pub start: Point,
+    // Where the guard walks on the unchanged map. Both parts need it, and a map the guard never
+    // leaves has no answer, so it's worked out while parsing
+    route: Vec<(Point, Direction)>,
+    jumps: OnceLock<Jumps>,
+}
+
+impl Map {
+    /// Every step of the guard's walk, in order: where they are and which way they step from there
+    pub fn route(&self) -> &[(Point, Direction)] {
+        &self.route
+    }
+
+    fn jumps(&self) -> &Jumps {
+        self.jumps.get_or_init(|| Jumps::new(&self.grid))
+    }
+
+    fn index(&self, p: Point) -> usize {
+        p.x as usize * self.grid.width() + p.y as usize
+    }
+}
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+
+    type Input = Map;
+    type Answer = usize;
+
+    fn parse(data: &str) -> Result<Self::Input, ParseError> {
+        let grid = Grid::parse(data, |c| matches!(c, '.' | '#' | '^').then_some(c))?;
+
+        let guards: Vec<Point> = grid.find_all(&'^').collect();
+        if guards.is_empty() {
+            return Err(ParseError::at(
+                data,
+                &data[..0],
+                "no guard (`^`) on the map",
+            ));
+        }
+        // Every cell is a single byte, so the column is also the byte offset
+        let cell = |p: Point| {
+            let line = data.lines().nth(p.x as usize).unwrap();
+            &line[p.y as usize..p.y as usize + 1]
+        };
+        if let Some(&p) = guards.get(1) {
+            return Err(ParseError::at(data, cell(p), "more than one guard"));
+        }
+        let start = guards[0];
+        let Some(route) = walk(&grid, start) else {
+            return Err(ParseError::at(
+                data,
+                cell(start),
+                "the guard never leaves the map",
+            ));
+        };
+        Ok(Map {
+            grid,
+            start,
+            route,
+            jumps: OnceLock::new(),
+        })
+    }
+
+    fn part1(map: &Self::Input) -> usize {
+        let mut visited = vec![false; map.grid.height() * map.grid.width()];
+        for &(p, _) in map.route() {
+            visited[map.index(p)] = true;
+        }
+        visited.into_iter().filter(|&v| v).count()
+    }
+
+    fn part2(map: &Self::Input) -> usize {
+        map.count_loops(parallel::available())
+    }
+}
+
+impl Map {
+    /// How many places a new obstacle traps the guard in a loop, trying them on `threads` threads
+    pub fn count_loops(&self, threads: NonZeroUsize) -> usize {
+        self.each_batch(threads, |obstacles| obstacles.count())
+            .into_iter()
+            .sum()
+    }
+
+    /// Every place a new obstacle traps the guard, in the order the guard would first reach them
+    pub fn loops(&self, threads: NonZeroUsize) -> Vec<Loop> {
+        let cells = self.jumps().cells;
+        self.each_batch(threads, |obstacles| {
+            let mut first_step = vec![u32::MAX; cells * 4];
+            obstacles
+                .map(|obstacle| self.describe(obstacle, &mut first_step))
+                .collect::<Vec<_>>()
+        })
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
+    // Split the candidates into batches across `threads` and hand `f` each batch's obstacles that
+    // cause a loop, in order. The results come back in batch order.
+    fn each_batch<R: Send>(
+        &self,
+        threads: NonZeroUsize,
+        f: impl Fn(&mut dyn Iterator<Item = usize>) -> R + Sync,
+    ) -> Vec<R> {
+        let jumps = self.jumps();
+        let candidates = self.candidates();
+
+        // Each candidate only takes microseconds, so hand them out in a few batches per thread
+        let batch = candidates.len().div_ceil(threads.get() * 4).max(1);
+        let batches: Vec<_> = candidates.chunks(batch).collect();
+        parallel::map(threads, &batches, |batch| {
+            let mut seen = States::new(jumps.cells);
+            let mut obstacles = batch
+                .iter()
+                .filter(|&&(from, direction, obstacle)| {
+                    jumps.loops(from, direction, obstacle, &mut seen)
+                })
+                .map(|&(_, _, obstacle)| obstacle);
+            f(&mut obstacles)
+        })
+    }
+
+    // Walk one cell at a time from the start with a wall at `obstacle`, which must cause a loop,
+    // until a state comes round again. `first_step` maps each state to when it was first seen,
+    // and is left all `u32::MAX` again afterwards.
+    fn describe(&self, obstacle: usize, first_step: &mut [u32]) -> Loop {
+        let width = self.grid.width();
+        let obstacle = Point::new((obstacle / width) as i32, (obstacle % width) as i32);
+        let state = |p: Point, direction| self.index(p) * 4 + heading(direction);
+
+        let mut walk = vec![];
+        let (mut position, mut direction) = (self.start, Direction::North);
+        let entry = loop {
+            let seen = &mut first_step[state(position, direction)];
+            if *seen != u32::MAX {
+                break *seen as usize;
+            }
+            *seen = walk.len() as u32;
+            walk.push((position, direction));
+
+            let ahead = position + direction;
+            if ahead == obstacle || self.grid.get(ahead) == Some(&'#') {
+                direction = direction.turn_right();
+            } else {
+                position = ahead;
+            }
+        };
+
+        for &(p, d) in &walk {
+            first_step[state(p, d)] = u32::MAX;
+        }
+        let lap = &walk[entry..];
+        Loop {
+            obstacle,
+            // Turning on the spot isn't a step
+            length: lap
+                .iter()
+                .zip(lap.iter().cycle().skip(1))
+                .filter(|(a, b)| a.0 != b.0)
+                .count(),
+            entry: walk[entry],
+        }
+    }
+
+    // Every cell worth putting an obstacle on, with where the guard is and which way they're
+    // heading just before they'd first walk into it
+    fn candidates(&self) -> Vec<(usize, Direction, usize)> {
+        // An obstacle has to go somewhere on the route, or the guard never meets it. The guard
+        // walks the same way up to the first time they'd step onto it, so start from there.
+        // Later visits to the same cell don't count again, and the start is where the guard is.
+        let mut tried = vec![false; self.grid.height() * self.grid.width()];
+        tried[self.index(self.start)] = true;
+
+        let mut candidates = vec![];
+        for &(p, direction) in self.route() {
+            let ahead = p + direction;
+            if !self.grid.contains(ahead) || std::mem::replace(&mut tried[self.index(ahead)], true)
+            {
+                continue;
+            }
+            candidates.push((self.index(p), direction, self.index(ahead)));
+        }
+        candidates
+    }
+
+    /// The map at `step` of the route, for animating: obstacles, the cells visited before this
+    /// step, and the guard showing which way they're about to go. Cropped to `rows` by `columns`
+    /// around the guard.
+    pub fn frame(&self, step: usize, rows: usize, columns: usize) -> String {
+        let route = self.route();
+        let step = step.min(route.len() - 1);
+        let mut visited = vec![false; self.grid.height() * self.grid.width()];
+        for &(p, _) in &route[..step] {
+            visited[self.index(p)] = true;
+        }
+
+        let (guard, heading) = route[step];
+        terminal::window(&self.grid, guard, rows, columns, |p, &c| {
+            if p == guard {
+                heading.to_char().unwrap().green().bold()
+            } else if c == '#' {
+                '#'.red()
+            } else if visited[self.index(p)] {
+                'X'.yellow()
+            } else {
+                '.'.dark_grey()
+            }
+        })
+    }
+}
+
+const FLOOR: Rgb = [25, 25, 35];
+const WALL: Rgb = [150, 150, 160];
+const TRAIL: Rgb = [230, 190, 60];
+const GUARD: Rgb = [60, 220, 90];
+const LOOP: Rgb = [230, 50, 50];
+
+impl Map {
+    /// The map once the guard has left, with their trail in yellow and each of `loops`' obstacles
+    /// in red
+    pub fn picture(&self, loops: &[Loop]) -> Grid<Rgb> {
+        let mut image = self.blank();
+        for &(p, _) in self.route() {
+            image.set(p, TRAIL);
+        }
+        for l in loops {
+            image.set(l.obstacle, LOOP);
+        }
+        image
+    }
+
+    /// The map at each step of the route, with the trail so far and the guard in green
+    pub fn trace(&self) -> impl Iterator<Item = Grid<Rgb>> + '_ {
+        let mut image = self.blank();
+        let mut last = None;
+        self.route().iter().map(move |&(p, _)| {
+            if let Some(last) = last.replace(p) {
+                image.set(last, TRAIL);
+            }
+            image.set(p, GUARD);
+            image.clone()
+        })
+    }
+
+    fn blank(&self) -> Grid<Rgb> {
+        picture::paint(&self.grid, |_, &c| if c == '#' { WALL } else { FLOOR })
+    }
+}
+
+/// A new obstacle that traps the guard
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Loop {
+    pub obstacle: Point,
+    /// Steps the guard takes to go round once
+    pub length: usize,
+    /// Where the guard first joins the loop, and which way they're heading
+    pub entry: (Point, Direction),
+}
+
+// The guard's whole walk from `start`, or `None` if they never leave
+fn walk(grid: &Grid<char>, start: Point) -> Option<Vec<(Point, Direction)>> {
+    let mut route = vec![];
+    let mut seen = States::new(grid.height() * grid.width());
+    let index = |p: Point| p.x as usize * grid.width() + p.y as usize;
+
+    let mut position = start;
+    let mut direction = Direction::North;
+    loop {
+        let ahead = position + direction;
+        match grid.get(ahead) {
+            None => {
+                route.push((position, direction));
+                return Some(route);
+            }
+            Some('#') => {
+                direction = direction.turn_right();
+                if !seen.insert(index(position), direction) {
+                    return None;
+                }
+            }
+            Some(_) => {
+                route.push((position, direction));
+                position = ahead;
+            }
+        }
+    }
+}
+
+const OFF_MAP: u32 = u32::MAX;
+
+// For every cell and heading, the cell the guard stops on in front of the next obstacle
+#[derive(Debug)]
+struct Jumps {
+    width: usize,
+    cells: usize,
+    // Indexed by `heading(direction)`, then cell. `OFF_MAP` if the guard walks off the map instead
+    stop: [Vec<u32>; 4],
+}
+
+impl Jumps {
+    fn new(grid: &Grid<char>) -> Self {
+        let (height, width) = (grid.height(), grid.width());
+        let cells = height * width;
+        let mut stop = [(); 4].map(|_| vec![OFF_MAP; cells]);
+
+        for direction in Direction::CARDINAL {
+            let table = &mut stop[heading(direction)];
+            // Fill from the edge the guard would walk off, so the cell ahead is always done
+            let mut points: Vec<Point> = grid.points().collect();
+            let step = direction.vector();
+            if step.x > 0 || step.y > 0 {
+                points.reverse();
+            }
+            for p in points {
+                let ahead = p + direction;
+                let i = p.x as usize * width + p.y as usize;
+                table[i] = match grid.get(ahead) {
+                    None => OFF_MAP,
+                    Some('#') => i as u32,
+                    Some(_) => table[ahead.x as usize * width + ahead.y as usize],
+                };
+            }
+        }
+        Jumps { width, cells, stop }
+    }
+
+    // Whether the guard, at `from` about to step `direction`, loops once `obstacle` is added
+    fn loops(&self, from: usize, direction: Direction, obstacle: usize, seen: &mut States) -> bool {
+        seen.clear();
+        let mut position = from;
+        let mut direction = direction;
+        loop {
+            let mut stop = self.stop[heading(direction)][position];
+            if self.blocks(position, stop, direction, obstacle) {
+                stop = (obstacle as isize - self.offset(direction)) as u32;
+            }
+            if stop == OFF_MAP {
+                return false;
+            }
+            position = stop as usize;
+            direction = direction.turn_right();
+            if !seen.insert(position, direction) {
+                return true;
+            }
+        }
+    }
+
+    // Whether `obstacle` is between `from` and where the guard would otherwise stop
+    fn blocks(&self, from: usize, stop: u32, direction: Direction, obstacle: usize) -> bool {
+        let (fx, fy) = (from / self.width, from % self.width);
+        let (ox, oy) = (obstacle / self.width, obstacle % self.width);
+        let off = stop == OFF_MAP;
+        let (sx, sy) = (stop as usize / self.width, stop as usize % self.width);
+        match direction {
+            Direction::North => oy == fy && ox < fx && (off || ox >= sx),
+            Direction::South => oy == fy && ox > fx && (off || ox <= sx),
+            Direction::West => ox == fx && oy < fy && (off || oy >= sy),
+            Direction::East => ox == fx && oy > fy && (off || oy <= sy),
+            _ => unreachable!("the guard only walks in cardinal directions"),
+        }
+    }
+
+    // How far one step in `direction` moves the cell index
+    fn offset(&self, direction: Direction) -> isize {
+        let step = direction.vector();
+        step.x as isize * self.width as isize + step.y as isize
+    }
+}
+
+fn heading(direction: Direction) -> usize {
+    direction as usize / 2
+}
+
+// A dense set of (cell, heading) states, cleared by undoing only what was set
+#[derive(Debug)]
+struct States {
+    bits: Vec<u64>,
+    set: Vec<usize>,
+}
+
+impl States {
+    fn new(cells: usize) -> Self {
+        States {
+            bits: vec![0; (cells * 4).div_ceil(64)],
+            set: vec![],
+        }
+    }
+
+    // False if the state was already there
+    fn insert(&mut self, cell: usize, direction: Direction) -> bool {
+        let state = cell * 4 + heading(direction);
+        let (word, bit) = (state / 64, 1 << (state % 64));
+        if self.bits[word] & bit != 0 {
+            return false;
+        }
+        self.bits[word] |= bit;
+        self.set.push(word);
+        true
+    }
+
+    fn clear(&mut self) {
+        for word in self.set.drain(..) {
+            self.bits[word] = 0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::tests::random;
+
+    // The original part2, kept to check the fast one against: clone the map for every cell on
+    // the route, put a wall there and walk from the start
+    fn part2_naive(map: &Map) -> usize {
+        let visited = get_visited(&map.grid, false).unwrap();
+        visited
+            .iter()
+            .filter(|&&p| {
+                if p == map.start {
+                    return false;
+                }
+                let mut new_grid = map.grid.clone();
+                new_grid.set(p, '#');
+                get_visited(&new_grid, true).is_none()
+            })
+            .count()
+    }
+
+    fn get_visited(grid: &Grid<char>, check_loop: bool) -> Option<HashSet<Point>> {
+        let start = grid.find(&'^').unwrap_or_default();
+
+        let mut direction = Direction::North;
+        let mut current_position = start;
+
+        let mut next_position = start + direction;
+        let mut visited: HashSet<Point> = std::collections::HashSet::new();
+        let mut seen: HashSet<(Point, Direction)> = std::collections::HashSet::new();
+
+        while grid.get(current_position).is_some() {
+            visited.insert(current_position);
+            let Some(&next) = grid.get(next_position) else {
+                break;
+            };
+            if next == '#' {
+                direction = direction.turn_right();
+            } else {
+                current_position = next_position;
+            }
+            if check_loop {
+                // If we have seen the same position and going the same direction, we are in a loop
+                if seen.contains(&(current_position, direction)) {
+                    return None;
+                }
+                seen.insert((current_position, direction));
+            }
+            next_position = current_position + direction;
+        }
+
+        Some(visited)
+    }
+
+    // Maps with about one wall in `density` cells that the guard walks off unaided
+    fn random_maps(count: usize, size: usize, density: u64) -> Vec<String> {
+        let mut next = random();
+
+        let mut maps = vec![];
+        while maps.len() < count {
+            let guard = (next() as usize % size, next() as usize % size);
+            let map: String = (0..size)
+                .map(|x| {
+                    let row: String = (0..size)
+                        .map(|y| match (x, y) {
+                            _ if (x, y) == guard => '^',
+                            _ if next().is_multiple_of(density) => '#',
+                            _ => '.',
+                        })
+                        .collect();
+                    row + "\n"
+                })
+                .collect();
+            if Day6::parse(&map).is_ok() {
+                maps.push(map);
+            }
+        }
+        maps
+    }
+
+    #[test]
+    fn no_guard() {
+        let err = Day6::parse("....\n.#..").unwrap_err();
+        assert_eq!(err.message, "no guard (`^`) on the map");
+    }
+
+    #[test]
+    fn guard_never_leaves() {
+        let err = Day6::parse(".#..\n...#\n#^..\n..#.\n").unwrap_err();
+        assert_eq!(err.message, "the guard never leaves the map");
+        assert_eq!((err.line, err.column), (3, 2));
+    }
+
+    #[test]
+    fn unknown_cell() {
+        let err = Day6::parse("..#.\n.^x.").unwrap_err();
+        assert_eq!((err.line, err.column, err.token.as_str()), (2, 3, "x"));
+    }
+
+    #[test]
+    fn matches_naive_on_random_maps() {
+        for map in random_maps(40, 24, 8) {
+            let map = Day6::parse(&map).unwrap();
+            assert_eq!(Day6::part2(&map), part2_naive(&map), "\n{}", map.grid);
+        }
+    }
+
+    #[test]
+    fn threads_agree() {
+        let example = Day6::parse(include_str!("../../data/6.example")).unwrap();
+        let threads = NonZeroUsize::new(4).unwrap();
+        assert_eq!(example.count_loops(NonZeroUsize::MIN), 6);
+        assert_eq!(example.count_loops(threads), 6);
+
+        for map in random_maps(10, 64, 10) {
+            let map = Day6::parse(&map).unwrap();
+            let count = map.count_loops(NonZeroUsize::MIN);
+            assert_eq!(map.count_loops(threads), count, "\n{}", map.grid);
+            assert_eq!(map.loops(threads).len(), count, "\n{}", map.grid);
+        }
+    }
+
+    #[test]
+    fn loop_details() {
+        let example = Day6::parse(include_str!("../../data/6.example")).unwrap();
+        let loops = example.loops(NonZeroUsize::new(2).unwrap());
+        let mut obstacles: Vec<_> = loops.iter().map(|l| (l.obstacle.x, l.obstacle.y)).collect();
+        obstacles.sort();
+        assert_eq!(obstacles, [(6, 3), (7, 6), (7, 7), (8, 1), (8, 3), (9, 7)]);
+
+        for l in loops {
+            // Going round `length` steps from the entry comes back to it
+            let mut grid = example.grid.clone();
+            grid.set(l.obstacle, '#');
+            let (mut p, mut d) = l.entry;
+            let mut steps = 0;
+            loop {
+                if grid[p + d] == '#' {
+                    d = d.turn_right();
+                } else {
+                    p += d;
+                    steps += 1;
+                }
+                if (p, d) == l.entry {
+                    break;
+                }
+            }
+            assert_eq!(steps, l.length, "{l:?}");
+        }
+    }
+
+    #[test]
+    fn frames() {
+        let map = Day6::parse(include_str!("../../data/6.example")).unwrap();
+        let ansi = regex::Regex::new("\x1b\\[[0-9;]*m").unwrap();
+        let plain = |step, rows, columns| {
+            ansi.replace_all(&map.frame(step, rows, columns), "")
+                .into_owned()
+        };
+
+        let first = plain(0, 10, 10);
+        assert_eq!(first.lines().nth(6), Some(".#..^....."));
+        assert!(!first.contains('X'));
+
+        // By the last step every cell but the guard's is trail, which is part1
+        let last = plain(usize::MAX, 10, 10);
+        assert_eq!(last.lines().nth(9), Some("......#v.."));
+        assert_eq!(last.matches('X').count() + 1, Day6::part1(&map));
+
+        // A smaller view still has the guard in it
+        let cropped = plain(map.route().len() / 2, 3, 4);
+        assert_eq!(cropped.lines().map(str::len).collect::<Vec<_>>(), [4, 4, 4]);
+        assert!(cropped.contains(['^', '>', 'v', '<']));
+    }
+
+    #[test]
+    fn pictures() {
+        let map = Day6::parse(include_str!("../../data/6.example")).unwrap();
+        let loops = map.loops(NonZeroUsize::MIN);
+        let image = map.picture(&loops);
+        let count = |image: &Grid<Rgb>, colour| image.iter().filter(|&(_, c)| *c == colour).count();
+        // Loop obstacles all go on the trail
+        assert_eq!(count(&image, TRAIL) + loops.len(), Day6::part1(&map));
+        assert_eq!(count(&image, LOOP), 6);
+
+        let trace: Vec<_> = map.trace().collect();
+        assert_eq!(trace.len(), map.route().len());
+        assert_eq!(trace[0].get(map.start), Some(&GUARD));
+        let last = trace.last().unwrap();
+        assert_eq!(count(last, GUARD), 1);
+        assert_eq!(count(last, TRAIL) + 1, Day6::part1(&map));
+    }
+}
@@ -0,0 +1,89 @@
+use crate::Day;
+
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+
+/// Every day the runner knows about, in order
+pub const DAYS: &[Day] = &[
+    Day::new::<day1::Day1>(),
+    Day::new::<day2::Day2>(),
+    Day::new::<day3::Day3>(),
+    Day::new::<day4::Day4>(),
+    Day::new::<day5::Day5>(),
+    Day::new::<day6::Day6>(),
+];
+
+/// Look up a registered day by its number
+pub fn get(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.number == number)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use super::*;
+    use crate::answers::{Answers, example_files};
+    use crate::input::data_dir;
+
+    // Every data/N.example and data/N.<name>.example against its answers in answers.toml, so a
+    // new case is a new file rather than a new test. Files left empty by `new-day` are skipped.
+    // Each is also run with its parts side by side, which must give the same answers.
+    #[test]
+    fn examples() {
+        let answers = Answers::load(&Answers::path()).unwrap();
+        let mut checked = 0;
+        let mut failures = vec![];
+
+        for (number, source) in example_files(&data_dir()).unwrap() {
+            let path = source.path(number);
+            let data = std::fs::read_to_string(&path).unwrap();
+            if data.trim().is_empty() {
+                continue;
+            }
+            let name = path.file_name().unwrap().to_string_lossy();
+
+            let expected: Vec<(u8, &str)> = [1, 2]
+                .into_iter()
+                .filter_map(|part| answers.get(number, &source, part).map(|a| (part, a)))
+                .collect();
+            let Some(day) = get(number) else {
+                failures.push(format!("{name}: day {number} is not registered"));
+                continue;
+            };
+            if expected.is_empty() {
+                failures.push(format!("{name}: no answers in answers.toml"));
+                continue;
+            }
+            let only = answers.only_part(number, &source);
+            let report = match day.run(&data, only) {
+                Ok(report) => report,
+                Err(e) => {
+                    failures.push(e.in_file(name.to_string()).to_string());
+                    continue;
+                }
+            };
+            let parallel = day
+                .run_parallel(&data, only, NonZeroUsize::new(4).unwrap())
+                .unwrap();
+
+            for (part, answer) in expected {
+                let actual = report.answer(part).unwrap_or_default();
+                if actual != answer {
+                    failures.push(format!("{name}: part{part} is {actual}, expected {answer}"));
+                }
+                if parallel.answer(part) != report.answer(part) {
+                    failures.push(format!("{name}: part{part} differs run in parallel"));
+                }
+                checked += 1;
+            }
+        }
+
+        assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+        assert!(checked > 0, "no examples found in {}", data_dir().display());
+    }
+}
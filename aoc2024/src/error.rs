@@ -0,0 +1,155 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// Something in the puzzle input didn't look like what the parser expected.
+///
+/// Line and column are 1-based, so they can be pasted straight into an editor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub file: Option<String>,
+    pub line: usize,
+    pub column: usize,
+    pub token: String,
+    pub message: String,
+    source_line: String,
+}
+
+impl ParseError {
+    /// Error at `token`, which must be a slice of `data`. Its position is worked out from where
+    /// the slice starts, so parsers only have to hand over the bit they couldn't make sense of.
+    pub fn at(data: &str, token: &str, message: impl Into<String>) -> Self {
+        let offset = (token.as_ptr() as usize)
+            .checked_sub(data.as_ptr() as usize)
+            .filter(|&o| o <= data.len())
+            .expect("token is not a slice of the input");
+
+        let line_start = data[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = data[offset..].find('\n').map_or(data.len(), |i| offset + i);
+
+        ParseError {
+            file: None,
+            line: data[..offset].matches('\n').count() + 1,
+            column: data[line_start..offset].chars().count() + 1,
+            token: token.to_string(),
+            message: message.into(),
+            source_line: data[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_string(),
+        }
+    }
+
+    pub fn empty() -> Self {
+        ParseError {
+            file: None,
+            line: 1,
+            column: 1,
+            token: String::new(),
+            message: "input is empty".to_string(),
+            source_line: String::new(),
+        }
+    }
+
+    /// Attach the name of the file the input came from
+    pub fn in_file(mut self, file: impl Into<String>) -> Self {
+        self.file = Some(file.into());
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let file = self.file.as_deref().unwrap_or("<input>");
+        write!(f, "{file}:{}:{}: {}", self.line, self.column, self.message)?;
+        if !self.token.is_empty() {
+            write!(f, ", found `{}`", self.token)?;
+        }
+        if !self.source_line.is_empty() {
+            let gutter = " ".repeat(self.line.to_string().len());
+            let marker = "^".repeat(self.token.chars().count().max(1));
+            write!(
+                f,
+                "\n{gutter} |\n{} | {}\n{gutter} | {}{marker}",
+                self.line,
+                self.source_line,
+                " ".repeat(self.column - 1)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Anything that can go wrong around running a solution: finding its input, parsing it, or
+/// reading the files that go with it
+#[derive(Debug)]
+pub enum Error {
+    Io(std::io::Error),
+    Parse(ParseError),
+    /// One of our own files, like `answers.toml`, is malformed
+    Config(String),
+    /// adventofcode.com (or whatever stands in for it) couldn't be reached or said no
+    Http(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(e) => write!(f, "{e}"),
+            Error::Parse(e) => write!(f, "{e}"),
+            Error::Config(e) => write!(f, "{e}"),
+            Error::Http(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Self {
+        Error::Parse(e)
+    }
+}
+
+/// Parse `token` (a slice of `data`) as a number, reporting where it was on failure
+pub fn parse_number<T: FromStr>(data: &str, token: &str) -> Result<T, ParseError> {
+    token
+        .parse::<T>()
+        .map_err(|_| ParseError::at(data, token, "expected a number"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn position_of_token() {
+        let data = "1   2\n3   x4\n";
+        let err = parse_number::<u64>(data, &data[10..12]).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 5));
+        assert_eq!(err.token, "x4");
+    }
+
+    #[test]
+    fn display_points_at_token() {
+        let data = "1   2\n3   x4";
+        let err = ParseError::at(data, &data[10..12], "expected a number").in_file("1.input");
+        assert_eq!(
+            err.to_string(),
+            "1.input:2:5: expected a number, found `x4`\n  |\n2 | 3   x4\n  |     ^^"
+        );
+    }
+
+    #[test]
+    fn token_at_end_of_input() {
+        let data = "47|";
+        let err = parse_number::<u32>(data, &data[3..]).unwrap_err();
+        assert_eq!((err.line, err.column), (1, 4));
+    }
+}
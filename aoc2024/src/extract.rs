@@ -0,0 +1,260 @@
+/*
+ * `extract N PAGE`: pull the example and its expected answers out of a saved puzzle page.
+ *
+ * Each part of the puzzle is an `<article class="day-desc">`. For each one we take:
+ *
+ *  - the first `<pre><code>` block, which is the example (later blocks are usually the example
+ *    again, part way through being solved)
+ *  - the last `<code><em>` in the text, which is the answer for that example
+ *
+ * Part 1's example becomes `data/N.example`. If part 2 brings a different example it becomes
+ * `data/N.second.example`, otherwise part 2's answer is for `data/N.example` too. The answers go
+ * into `answers.toml`.
+ */
+use std::path::{Path, PathBuf};
+
+use crate::Error;
+use crate::answers::{self, Source};
+use crate::input::read_file;
+
+/// What one part of the puzzle page says
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartExample {
+    pub example: Option<String>,
+    pub answer: Option<String>,
+}
+
+/// The example and answer for each part on the page, part 1 first
+pub fn parse_page(html: &str) -> Vec<PartExample> {
+    let articles = between(html, "<article class=\"day-desc\">", "</article>");
+    let articles = if articles.is_empty() {
+        vec![html]
+    } else {
+        articles
+    };
+
+    articles
+        .into_iter()
+        .map(|article| PartExample {
+            example: between(article, "<pre><code>", "</code></pre>")
+                .first()
+                .map(|block| text(block)),
+            answer: emphasised_code(article).last().map(|answer| text(answer)),
+        })
+        .collect()
+}
+
+/// An example file to write and the answers that go with it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example<'a> {
+    pub source: Source,
+    pub text: Option<&'a str>,
+    pub answers: Vec<(u8, String)>,
+}
+
+/// Where each example goes and which answers belong to it
+pub fn examples(parts: &[PartExample]) -> Vec<Example<'_>> {
+    let mut examples: Vec<Example> = vec![];
+    for (part, found) in (1..=2).zip(parts) {
+        let example = found.example.as_deref();
+        let answers = found.answer.iter().map(|a| (part, a.clone())).collect();
+
+        // A part without its own example, or repeating the last one, is answered on that one
+        match examples.last_mut() {
+            Some(last) if example.is_none() || example == last.text => last.answers.extend(answers),
+            _ => {
+                let source = if examples.is_empty() {
+                    Source::Example
+                } else {
+                    Source::Extra("second".to_string())
+                };
+                examples.push(Example {
+                    source,
+                    text: example,
+                    answers,
+                });
+            }
+        }
+    }
+    examples
+}
+
+/// Extract the examples and answers from the page at `page` into the crate at `root`, returning
+/// the files written. An example file is only written if it's missing or empty, as `new-day`
+/// leaves it.
+pub fn extract(root: &Path, day: u8, page: &Path) -> Result<Vec<PathBuf>, Error> {
+    let html = read_file(page)?;
+    let parts = parse_page(&html);
+    if parts.iter().all(|p| p.example.is_none()) {
+        return Err(Error::Config(format!(
+            "{}: no <pre><code> example found",
+            page.display()
+        )));
+    }
+
+    let answers_path = root.join("answers.toml");
+    let original = std::fs::read_to_string(&answers_path)?;
+    let mut answers_toml = original.clone();
+    let mut written = vec![];
+    for Example {
+        source,
+        text,
+        answers,
+    } in examples(&parts)
+    {
+        if let Some(example) = text {
+            let path = root.join("data").join(source.file_name(day));
+            match std::fs::read_to_string(&path) {
+                Ok(existing) if existing == example => {}
+                Ok(existing) if !existing.trim().is_empty() => {
+                    return Err(Error::Config(format!(
+                        "{} already exists with a different example",
+                        path.display()
+                    )));
+                }
+                _ => {
+                    std::fs::write(&path, example)?;
+                    written.push(path);
+                }
+            }
+        }
+        answers_toml = answers::add(&answers_toml, day, &source, &answers)
+            .map_err(|e| Error::Config(format!("{}: {e}", answers_path.display())))?;
+    }
+
+    if answers_toml != original {
+        std::fs::write(&answers_path, answers_toml)?;
+        written.push(answers_path);
+    }
+    Ok(written)
+}
+
+// The text between each `start` and the following `end`
+fn between<'a>(text: &'a str, start: &str, end: &str) -> Vec<&'a str> {
+    let mut found = vec![];
+    let mut rest = text;
+    while let Some(at) = rest.find(start) {
+        rest = &rest[at + start.len()..];
+        let Some(stop) = rest.find(end) else {
+            break;
+        };
+        found.push(&rest[..stop]);
+        rest = &rest[stop + end.len()..];
+    }
+    found
+}
+
+// Answers are written `<code><em>42</em></code>`, occasionally the other way round. Code with only
+// some of it emphasised is working, not an answer.
+fn emphasised_code(article: &str) -> Vec<&str> {
+    let mut found: Vec<(usize, &str)> = vec![];
+    for (open, close) in [
+        ("<code><em>", "</em></code>"),
+        ("<em><code>", "</code></em>"),
+    ] {
+        for inner in between(article, open, close) {
+            if inner.contains('<') {
+                continue;
+            }
+            let offset = inner.as_ptr() as usize - article.as_ptr() as usize;
+            found.push((offset, inner));
+        }
+    }
+    found.sort();
+    found.into_iter().map(|(_, inner)| inner).collect()
+}
+
+// Drop any tags (examples highlight bits with `<em>`) and decode the entities the site uses
+fn text(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&#x27;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::answers::Answers;
+    use crate::tests::TempDir;
+
+    const PAGE: &str = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 3: Mull It Over ---</h2>
+<p>For example, consider the following section of corrupted memory:</p>
+<pre><code>x<em>mul(2,4)</em>%&amp;mul[3,7]!@^do_not_<em>mul(5,5)</em>+mul(32,64]then(<em>mul(11,8)</em><em>mul(8,5)</em>)</code></pre>
+<p>Adding up the result of each instruction produces <code><em>161</em></code> (<code>2*4 + 5*5 + 11*8 + 8*5</code>).</p>
+</article>
+<p>Your puzzle answer was <code>12345</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<pre><code>x<em>mul(2,4)</em>&amp;mul[3,7]!^<em>don't()</em>_mul(5,5)+mul(32,64](mul(11,8)un<em>do()</em>?<em>mul(8,5)</em>)</code></pre>
+<p>This time, the sum of the results is <code><em>48</em></code> (<code><em>2</em>*<em>4</em> + <em>8</em>*<em>5</em></code>).</p>
+</article>
+</main></body></html>"#;
+
+    #[test]
+    fn parts() {
+        let parts = parse_page(PAGE);
+        assert_eq!(parts.len(), 2);
+        assert_eq!(
+            parts[0].example.as_deref(),
+            Some("xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))")
+        );
+        assert_eq!(parts[0].answer.as_deref(), Some("161"));
+        assert!(parts[1].example.as_ref().unwrap().contains("don't()"));
+        assert_eq!(parts[1].answer.as_deref(), Some("48"));
+    }
+
+    #[test]
+    fn part2_on_the_same_example() {
+        let page = "<article class=\"day-desc\"><pre><code>3   4\n4   3\n</code></pre>\
+            <p>a total distance of <code><em>11</em></code>!</p></article>\
+            <article class=\"day-desc\"><p>similarity score is <em><code>31</code></em>.</p></article>";
+        let parts = parse_page(page);
+        let examples = examples(&parts);
+        assert_eq!(examples.len(), 1);
+        assert_eq!(examples[0].source, Source::Example);
+        assert_eq!(examples[0].text, Some("3   4\n4   3\n"));
+        assert_eq!(
+            examples[0].answers,
+            [(1, "11".to_string()), (2, "31".to_string())]
+        );
+    }
+
+    #[test]
+    fn writes_files() {
+        let root = TempDir::new("extract");
+        std::fs::create_dir_all(root.join("data")).unwrap();
+        std::fs::write(root.join("data/3.example"), "").unwrap();
+        std::fs::write(root.join("answers.toml"), "[1.example]\npart1 = 11\n").unwrap();
+        let page = root.join("day3.html");
+        std::fs::write(&page, PAGE).unwrap();
+
+        let written = extract(&root, 3, &page).unwrap();
+        assert_eq!(written.len(), 3);
+        assert!(
+            std::fs::read_to_string(root.join("data/3.second.example"))
+                .unwrap()
+                .contains("don't()")
+        );
+        let answers = Answers::load(&root.join("answers.toml")).unwrap();
+        assert_eq!(answers.get(1, &Source::Example, 1), Some("11"));
+        assert_eq!(answers.get(3, &Source::Example, 1), Some("161"));
+        let second = Source::Extra("second".to_string());
+        assert_eq!(answers.get(3, &second, 2), Some("48"));
+
+        // Running it again changes nothing
+        assert!(extract(&root, 3, &page).unwrap().is_empty());
+    }
+}
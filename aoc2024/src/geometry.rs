@@ -0,0 +1,287 @@
+/*
+ * Points, vectors and compass directions on a grid.
+ *
+ * Same convention as `Grid`: `x` is the row (growing downwards, so North is -x) and `y` is the
+ * column (growing to the right, so East is +y).
+ */
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq, PartialOrd, Ord, Default)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+/// The offset between two points. Same representation, the name just says how it's used.
+pub type Vector = Point;
+
+impl Point {
+    pub const ORIGIN: Point = Point::new(0, 0);
+
+    pub const fn new(x: i32, y: i32) -> Self {
+        Point { x, y }
+    }
+
+    /// Steps between two points moving only orthogonally
+    pub fn manhattan(self, other: Point) -> i32 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    /// Steps between two points when diagonal moves are allowed too
+    pub fn chebyshev(self, other: Point) -> i32 {
+        (self.x - other.x).abs().max((self.y - other.y).abs())
+    }
+
+    /// This vector turned a quarter clockwise, e.g. North to East
+    pub fn rotate_right(self) -> Vector {
+        Point::new(self.y, -self.x)
+    }
+
+    /// This vector turned a quarter anticlockwise, e.g. North to West
+    pub fn rotate_left(self) -> Vector {
+        Point::new(-self.y, self.x)
+    }
+
+    /// Turn this point a quarter clockwise around `center`
+    pub fn rotate_right_around(self, center: Point) -> Point {
+        center + (self - center).rotate_right()
+    }
+
+    /// Turn this point a quarter anticlockwise around `center`
+    pub fn rotate_left_around(self, center: Point) -> Point {
+        center + (self - center).rotate_left()
+    }
+
+    /// The 8 points around this one, clockwise from North
+    pub fn neighbours8(self) -> impl Iterator<Item = Point> {
+        Direction::ALL.into_iter().map(move |d| self + d)
+    }
+
+    /// The 4 points orthogonally next to this one, clockwise from North
+    pub fn neighbours4(self) -> impl Iterator<Item = Point> {
+        Direction::CARDINAL.into_iter().map(move |d| self + d)
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Vector) -> Self::Output {
+        Point::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, rhs: Vector) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Point {
+    type Output = Vector;
+
+    fn sub(self, rhs: Point) -> Self::Output {
+        Point::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, rhs: Vector) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<i32> for Point {
+    type Output = Vector;
+
+    fn mul(self, rhs: i32) -> Self::Output {
+        Point::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl Neg for Point {
+    type Output = Vector;
+
+    fn neg(self) -> Self::Output {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+impl Add<Direction> for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Direction) -> Self::Output {
+        self + rhs.vector()
+    }
+}
+
+impl AddAssign<Direction> for Point {
+    fn add_assign(&mut self, rhs: Direction) {
+        *self = *self + rhs;
+    }
+}
+
+/// The 8 compass directions, clockwise from North
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 8] = [
+        Direction::North,
+        Direction::NorthEast,
+        Direction::East,
+        Direction::SouthEast,
+        Direction::South,
+        Direction::SouthWest,
+        Direction::West,
+        Direction::NorthWest,
+    ];
+
+    pub const CARDINAL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    pub const DIAGONAL: [Direction; 4] = [
+        Direction::NorthEast,
+        Direction::SouthEast,
+        Direction::SouthWest,
+        Direction::NorthWest,
+    ];
+
+    /// One step in this direction
+    pub const fn vector(self) -> Vector {
+        match self {
+            Direction::North => Point::new(-1, 0),
+            Direction::NorthEast => Point::new(-1, 1),
+            Direction::East => Point::new(0, 1),
+            Direction::SouthEast => Point::new(1, 1),
+            Direction::South => Point::new(1, 0),
+            Direction::SouthWest => Point::new(1, -1),
+            Direction::West => Point::new(0, -1),
+            Direction::NorthWest => Point::new(-1, -1),
+        }
+    }
+
+    /// Turn clockwise by `eighths` of a full turn; negative turns anticlockwise
+    pub fn rotate(self, eighths: i32) -> Self {
+        Direction::ALL[(self as i32 + eighths).rem_euclid(8) as usize]
+    }
+
+    /// A quarter turn clockwise, e.g. North to East
+    pub fn turn_right(self) -> Self {
+        self.rotate(2)
+    }
+
+    /// A quarter turn anticlockwise, e.g. North to West
+    pub fn turn_left(self) -> Self {
+        self.rotate(-2)
+    }
+
+    pub fn reverse(self) -> Self {
+        self.rotate(4)
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self as u8 % 2 == 1
+    }
+
+    /// Parse the arrows used for guards and robots: `^`, `>`, `v` or `<`
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            '^' => Some(Direction::North),
+            '>' => Some(Direction::East),
+            'v' => Some(Direction::South),
+            '<' => Some(Direction::West),
+            _ => None,
+        }
+    }
+
+    /// The arrow for a cardinal direction, the inverse of [`Direction::from_char`]
+    pub fn to_char(self) -> Option<char> {
+        match self {
+            Direction::North => Some('^'),
+            Direction::East => Some('>'),
+            Direction::South => Some('v'),
+            Direction::West => Some('<'),
+            _ => None,
+        }
+    }
+}
+
+impl From<Direction> for Vector {
+    fn from(d: Direction) -> Self {
+        d.vector()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let a = Point::new(1, 2);
+        let b = Point::new(4, -2);
+        assert_eq!(a + b, Point::new(5, 0));
+        assert_eq!(b - a, Point::new(3, -4));
+        assert_eq!(a * 3, Point::new(3, 6));
+        assert_eq!(-a, Point::new(-1, -2));
+        assert_eq!(a + Direction::North, Point::new(0, 2));
+    }
+
+    #[test]
+    fn distances() {
+        let a = Point::new(1, 2);
+        let b = Point::new(4, -2);
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+    }
+
+    #[test]
+    fn turns() {
+        assert_eq!(Direction::North.turn_right(), Direction::East);
+        assert_eq!(Direction::West.turn_right(), Direction::North);
+        assert_eq!(Direction::North.turn_left(), Direction::West);
+        assert_eq!(Direction::NorthEast.reverse(), Direction::SouthWest);
+        assert_eq!(Direction::North.rotate(-1), Direction::NorthWest);
+        for d in Direction::ALL {
+            assert_eq!(d.vector().rotate_right(), d.turn_right().vector());
+            assert_eq!(d.vector().rotate_left(), d.turn_left().vector());
+            assert_eq!(-d.vector(), d.reverse().vector());
+        }
+    }
+
+    #[test]
+    fn rotate_around() {
+        let center = Point::new(1, 1);
+        assert_eq!(
+            Point::new(0, 1).rotate_right_around(center),
+            Point::new(1, 2)
+        );
+        assert_eq!(
+            Point::new(0, 1).rotate_left_around(center),
+            Point::new(1, 0)
+        );
+    }
+
+    #[test]
+    fn chars() {
+        for d in Direction::CARDINAL {
+            assert_eq!(Direction::from_char(d.to_char().unwrap()), Some(d));
+        }
+        assert_eq!(Direction::SouthEast.to_char(), None);
+        assert_eq!(Direction::from_char('#'), None);
+    }
+}
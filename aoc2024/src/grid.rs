@@ -0,0 +1,312 @@
+/*
+ * A rectangular grid of cells stored in one flat buffer, row by row.
+ *
+ * Same convention as the puzzles so far: `x` is the row (vertical axis, growing downwards) and
+ * `y` is the column (horizontal axis, growing to the right).
+ */
+use std::fmt;
+
+use crate::error::ParseError;
+use crate::geometry::{Direction, Point, Vector};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    height: usize, // X
+    width: usize,  // Y
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(height: usize, width: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            height,
+            width,
+            cells: vec![fill; height * width],
+        }
+    }
+
+    /// Parse one row per line, turning each character into a cell with `cell`. A `None` from
+    /// `cell` is reported as an unexpected character at that position. Blank lines at the end are
+    /// ignored, but not in the middle.
+    pub fn parse(data: &str, mut cell: impl FnMut(char) -> Option<T>) -> Result<Self, ParseError> {
+        let mut height = 0;
+        let mut width = None;
+        let mut cells = Vec::new();
+
+        for line in data.trim_end_matches(['\n', '\r']).lines() {
+            let mut row_width = 0;
+            for (i, c) in line.char_indices() {
+                let value = cell(c).ok_or_else(|| {
+                    ParseError::at(data, &line[i..i + c.len_utf8()], "unexpected character")
+                })?;
+                cells.push(value);
+                row_width += 1;
+            }
+
+            match width {
+                None => width = Some(row_width),
+                Some(w) if w != row_width => {
+                    return Err(ParseError::at(
+                        data,
+                        line,
+                        format!("expected a row of {w} cells"),
+                    ));
+                }
+                Some(_) => {}
+            }
+            height += 1;
+        }
+
+        match width {
+            Some(width) if width > 0 => Ok(Grid {
+                height,
+                width,
+                cells,
+            }),
+            _ => Err(ParseError::empty()),
+        }
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        p.x >= 0 && p.y >= 0 && (p.x as usize) < self.height && (p.y as usize) < self.width
+    }
+
+    fn offset(&self, p: Point) -> Option<usize> {
+        self.contains(p)
+            .then(|| p.x as usize * self.width + p.y as usize)
+    }
+
+    pub fn get(&self, p: Point) -> Option<&T> {
+        self.offset(p).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        self.offset(p).map(|i| &mut self.cells[i])
+    }
+
+    /// Replace the cell at `p`, returning the old value, or `None` if `p` is off the grid
+    pub fn set(&mut self, p: Point, value: T) -> Option<T> {
+        self.get_mut(p).map(|cell| std::mem::replace(cell, value))
+    }
+
+    /// Every point on the grid, row by row
+    pub fn points(&self) -> impl Iterator<Item = Point> + use<T> {
+        let width = self.width;
+        (0..self.height * width).map(move |i| Point::new((i / width) as i32, (i % width) as i32))
+    }
+
+    /// Every cell with its point, row by row
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(&self.cells)
+    }
+
+    pub fn row(&self, x: usize) -> &[T] {
+        &self.cells[x * self.width..(x + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width)
+    }
+
+    pub fn column(&self, y: usize) -> impl Iterator<Item = &T> {
+        self.cells.iter().skip(y).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|y| self.column(y))
+    }
+
+    /// Cells from `start` (inclusive) stepping by `step` until falling off the grid
+    pub fn ray(&self, start: Point, step: Vector) -> impl Iterator<Item = (Point, &T)> {
+        std::iter::successors(Some(start), move |&p| Some(p + step))
+            .map_while(|p| self.get(p).map(|cell| (p, cell)))
+    }
+
+    /// Diagonals running down and to the right, starting from the bottom-left corner
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let (height, width) = (self.height as i32, self.width as i32);
+        let starts = (0..height)
+            .rev()
+            .map(|x| Point::new(x, 0))
+            .chain((1..width).map(|y| Point::new(0, y)));
+        starts.map(|start| {
+            self.ray(start, Direction::SouthEast.vector())
+                .map(|(_, c)| c)
+        })
+    }
+
+    /// Diagonals running down and to the left, starting from the top-left corner
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let (height, width) = (self.height as i32, self.width as i32);
+        let starts = (0..width)
+            .map(|y| Point::new(0, y))
+            .chain((1..height).map(move |x| Point::new(x, width - 1)));
+        starts.map(|start| {
+            self.ray(start, Direction::SouthWest.vector())
+                .map(|(_, c)| c)
+        })
+    }
+
+    /// The up-to-4 orthogonal neighbours of `p` that are on the grid
+    pub fn neighbours4(&self, p: Point) -> impl Iterator<Item = Point> {
+        p.neighbours4().filter(|&n| self.contains(n))
+    }
+
+    /// The up-to-8 neighbours of `p`, including diagonals, that are on the grid
+    pub fn neighbours8(&self, p: Point) -> impl Iterator<Item = Point> {
+        p.neighbours8().filter(|&n| self.contains(n))
+    }
+
+    /// First point, row by row, holding `value`
+    pub fn find(&self, value: &T) -> Option<Point>
+    where
+        T: PartialEq,
+    {
+        self.iter().find(|(_, c)| *c == value).map(|(p, _)| p)
+    }
+
+    /// Every point holding `value`, row by row
+    pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Point> + 'a
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .filter(move |(_, c)| *c == value)
+            .map(|(p, _)| p)
+    }
+
+    /// A grid of the same shape with every cell passed through `f`
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            height: self.height,
+            width: self.width,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T> std::ops::Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point) -> &Self::Output {
+        self.get(p)
+            .unwrap_or_else(|| panic!("{p:?} is outside a {}x{} grid", self.height, self.width))
+    }
+}
+
+impl<T> std::ops::IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, p: Point) -> &mut Self::Output {
+        let (height, width) = (self.height, self.width);
+        self.get_mut(p)
+            .unwrap_or_else(|| panic!("{p:?} is outside a {height}x{width} grid"))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<char> {
+        Grid::parse("abc\ndef", Some).unwrap()
+    }
+
+    #[test]
+    fn parse_and_index() {
+        let grid = grid();
+        assert_eq!((grid.height(), grid.width()), (2, 3));
+        assert_eq!(grid[Point::new(1, 2)], 'f');
+        assert_eq!(grid.get(Point::new(2, 0)), None);
+        assert_eq!(grid.get(Point::new(0, -1)), None);
+        assert_eq!(grid.to_string(), "abc\ndef\n");
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = Grid::parse("ab\nabc", Some).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+
+        let err = Grid::parse("ab\na#", |c| (c != '#').then_some(c)).unwrap_err();
+        assert_eq!((err.line, err.column, err.token.as_str()), (2, 2, "#"));
+
+        assert_eq!(Grid::parse("", Some).unwrap_err(), ParseError::empty());
+        assert_eq!(Grid::parse("\n\n", Some).unwrap_err(), ParseError::empty());
+
+        // A trailing blank line is fine, one between rows isn't
+        assert_eq!(Grid::parse("ab\n\n", Some).unwrap().height(), 1);
+        assert_eq!(Grid::parse("ab\r\n\r\n", Some).unwrap().height(), 1);
+        let err = Grid::parse("ab\n\nab\n", Some).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+    }
+
+    #[test]
+    fn set_checks_bounds() {
+        let mut grid = grid();
+        assert_eq!(grid.set(Point::new(0, 0), 'z'), Some('a'));
+        assert_eq!(grid.set(Point::new(5, 0), 'z'), None);
+        assert_eq!(grid.row(0), ['z', 'b', 'c']);
+    }
+
+    #[test]
+    fn lines() {
+        let grid = grid();
+        let collect = |it: &mut dyn Iterator<Item = &char>| it.collect::<String>();
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        assert_eq!(
+            grid.columns()
+                .map(|mut c| collect(&mut c))
+                .collect::<Vec<_>>(),
+            ["ad", "be", "cf"]
+        );
+        assert_eq!(
+            grid.diagonals()
+                .map(|mut c| collect(&mut c))
+                .collect::<Vec<_>>(),
+            ["d", "ae", "bf", "c"]
+        );
+        assert_eq!(
+            grid.anti_diagonals()
+                .map(|mut c| collect(&mut c))
+                .collect::<Vec<_>>(),
+            ["a", "bd", "ce", "f"]
+        );
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = grid();
+        assert_eq!(grid.neighbours4(Point::new(0, 0)).count(), 2);
+        assert_eq!(grid.neighbours8(Point::new(0, 0)).count(), 3);
+        assert_eq!(grid.neighbours8(Point::new(1, 1)).count(), 5);
+    }
+
+    #[test]
+    fn find_values() {
+        let grid = Grid::parse("a.a\n.a.", Some).unwrap();
+        assert_eq!(grid.find(&'.'), Some(Point::new(0, 1)));
+        assert_eq!(grid.find_all(&'a').count(), 3);
+        assert_eq!(grid.find(&'z'), None);
+    }
+}
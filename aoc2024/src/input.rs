@@ -0,0 +1,123 @@
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use crate::{Error, Solution};
+
+/// Where to read a day's puzzle input from. Shared by the runner and every `dayN` binary.
+#[derive(clap::Parser, Debug, Default, Clone)]
+pub struct InputArgs {
+    /// Read the input from this file, or `-` for stdin
+    #[arg(long, value_name = "PATH", conflicts_with = "example")]
+    pub input: Option<String>,
+
+    /// Use `data/N.example` instead of `data/N.input`
+    #[arg(long)]
+    pub example: bool,
+}
+
+impl InputArgs {
+    /// True when the input is the same for every day, e.g. a single file or stdin
+    pub fn is_explicit(&self) -> bool {
+        self.input.is_some()
+    }
+
+    pub fn read(&self, day: u8) -> std::io::Result<String> {
+        match self.path(day) {
+            Some(path) => read_file(&path),
+            None => {
+                let mut data = String::new();
+                std::io::stdin().read_to_string(&mut data)?;
+                Ok(data)
+            }
+        }
+    }
+
+    /// Read and parse the input for `S`, naming the file in any parse error
+    pub fn load<S: Solution>(&self) -> Result<S::Input, Error> {
+        let data = self.read(S::DAY)?;
+        Ok(S::parse(&data).map_err(|e| e.in_file(self.name(S::DAY)))?)
+    }
+
+    /// How the input is referred to in messages
+    pub fn name(&self, day: u8) -> String {
+        match self.path(day) {
+            Some(path) => path.display().to_string(),
+            None => "<stdin>".to_string(),
+        }
+    }
+
+    // `None` means stdin
+    fn path(&self, day: u8) -> Option<PathBuf> {
+        match self.input.as_deref() {
+            Some("-") => None,
+            Some(path) => Some(resolve(Path::new(path))),
+            None if self.example => Some(example_path(day)),
+            None => Some(input_path(day)),
+        }
+    }
+}
+
+/// The crate's `data/` directory, independent of the current working directory
+pub fn data_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("data")
+}
+
+pub fn input_path(day: u8) -> PathBuf {
+    data_dir().join(format!("{day}.input"))
+}
+
+pub fn example_path(day: u8) -> PathBuf {
+    data_dir().join(format!("{day}.example"))
+}
+
+// Relative paths that don't exist from the cwd are looked up from the crate root instead, so
+// `--input data/1.other` works from both `aoc2024/` and the workspace root.
+fn resolve(path: &Path) -> PathBuf {
+    if path.is_relative() && !path.exists() {
+        let from_root = Path::new(env!("CARGO_MANIFEST_DIR")).join(path);
+        if from_root.exists() {
+            return from_root;
+        }
+    }
+    path.to_path_buf()
+}
+
+/// `path`'s contents, with the path in any error
+pub(crate) fn read_file(path: &Path) -> std::io::Result<String> {
+    std::fs::read_to_string(path)
+        .map_err(|e| std::io::Error::new(e.kind(), format!("{}: {e}", path.display())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_resolves_from_crate_root() {
+        let args = InputArgs {
+            input: None,
+            example: true,
+        };
+        let data = args.read(1).unwrap();
+        assert!(data.starts_with("3   4"));
+    }
+
+    #[test]
+    fn relative_input_resolves_from_crate_root() {
+        let args = InputArgs {
+            input: Some("data/2.example".to_string()),
+            example: false,
+        };
+        assert!(args.read(2).unwrap().starts_with("7 6 4 2 1"));
+    }
+
+    #[test]
+    fn missing_file_reports_path() {
+        let args = InputArgs {
+            input: Some("data/does-not-exist".to_string()),
+            example: false,
+        };
+        let err = args.read(1).unwrap_err();
+        assert!(err.to_string().contains("does-not-exist"));
+    }
+}
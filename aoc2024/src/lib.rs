@@ -0,0 +1,245 @@
+use std::fmt::Display;
+use std::num::NonZeroUsize;
+use std::time::{Duration, Instant};
+
+pub mod answers;
+pub mod client;
+pub mod days;
+pub mod error;
+pub mod extract;
+pub mod geometry;
+pub mod grid;
+pub mod input;
+pub mod output;
+pub mod parallel;
+pub mod pattern;
+pub mod picture;
+pub mod scaffold;
+pub mod submit;
+pub mod svg;
+pub mod terminal;
+pub mod wordsearch;
+
+pub use error::{Error, ParseError};
+
+/// Shared shape of every day: parse the puzzle input once, then solve each part from it.
+///
+/// Work both parts need belongs in `parse`, like day6's route. Work only one part needs can sit in
+/// the input behind a `OnceLock` and be done the first time that part asks, like day6's jump
+/// tables, which only part2 uses.
+pub trait Solution {
+    const DAY: u8;
+
+    /// Shared between threads when both parts run at once
+    type Input: Sync;
+    type Answer: Display;
+
+    fn parse(data: &str) -> Result<Self::Input, ParseError>;
+    fn part1(input: &Self::Input) -> Self::Answer;
+    fn part2(input: &Self::Input) -> Self::Answer;
+}
+
+/// A [`Solution`] with its types erased, so days with different inputs and answers can share a
+/// registry.
+pub struct Day {
+    pub number: u8,
+    run: RunFn,
+}
+
+// `run::<S>` for the day's solution: the data, which part, and how many threads it may use
+type RunFn = fn(&str, Option<u8>, Option<NonZeroUsize>) -> Result<Report, ParseError>;
+
+impl Day {
+    pub const fn new<S: Solution>() -> Self {
+        Day {
+            number: S::DAY,
+            run: run::<S>,
+        }
+    }
+
+    /// Parse `data` and solve the requested part, or both when `part` is `None`
+    pub fn run(&self, data: &str, part: Option<u8>) -> Result<Report, ParseError> {
+        (self.run)(data, part, None)
+    }
+
+    /// Like [`Day::run`], but using at most `threads` threads, including this one. With one,
+    /// everything runs in order on this thread. With more, both parts are solved at the same time
+    /// and split the threads between them.
+    pub fn run_parallel(
+        &self,
+        data: &str,
+        part: Option<u8>,
+        threads: NonZeroUsize,
+    ) -> Result<Report, ParseError> {
+        (self.run)(data, part, Some(threads))
+    }
+}
+
+/// Answers for one day, with how long each phase took
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub day: u8,
+    pub parse: Duration,
+    pub part1: Option<Timed>,
+    pub part2: Option<Timed>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Timed {
+    pub answer: String,
+    pub elapsed: Duration,
+}
+
+impl Report {
+    pub fn answer(&self, part: u8) -> Option<&str> {
+        let timed = if part == 1 { &self.part1 } else { &self.part2 };
+        timed.as_ref().map(|t| t.answer.as_str())
+    }
+
+    /// Time spent on `part`, zero if it wasn't run
+    pub fn elapsed(&self, part: u8) -> Duration {
+        let timed = if part == 1 { &self.part1 } else { &self.part2 };
+        timed.as_ref().map_or(Duration::ZERO, |t| t.elapsed)
+    }
+
+    pub fn total(&self) -> Duration {
+        self.parse + self.elapsed(1) + self.elapsed(2)
+    }
+}
+
+fn run<S: Solution>(
+    data: &str,
+    part: Option<u8>,
+    threads: Option<NonZeroUsize>,
+) -> Result<Report, ParseError> {
+    let start = Instant::now();
+    let input = S::parse(data)?;
+    let parse = start.elapsed();
+
+    // A part gets `threads` to itself for anything it runs in parallel, or the whole machine
+    let timed = |solve: fn(&S::Input) -> S::Answer, threads: Option<NonZeroUsize>| {
+        let start = Instant::now();
+        let answer = match threads {
+            Some(threads) => parallel::with_budget(threads, || solve(&input)),
+            None => solve(&input),
+        }
+        .to_string();
+        Timed {
+            answer,
+            elapsed: start.elapsed(),
+        }
+    };
+
+    let (part1, part2) = match threads {
+        Some(threads) if threads.get() > 1 && part.is_none() => std::thread::scope(|scope| {
+            let half = NonZeroUsize::new(threads.get() / 2).unwrap();
+            let rest = NonZeroUsize::new(threads.get() - half.get()).unwrap();
+            let part2 = scope.spawn(move || timed(S::part2, Some(rest)));
+            let part1 = timed(S::part1, Some(half));
+            let part2 = part2
+                .join()
+                .unwrap_or_else(|panic| std::panic::resume_unwind(panic));
+            (Some(part1), Some(part2))
+        }),
+        _ => (
+            (part != Some(2)).then(|| timed(S::part1, threads)),
+            (part != Some(1)).then(|| timed(S::part2, threads)),
+        ),
+    };
+
+    Ok(Report {
+        day: S::DAY,
+        parse,
+        part1,
+        part2,
+    })
+}
+
+/// Print an error the way a command line user expects, and give up
+pub fn exit_with(error: impl Display) -> ! {
+    eprintln!("error: {error}");
+    std::process::exit(1);
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::path::{Path, PathBuf};
+
+    use super::*;
+
+    /// An empty directory of its own for a test, removed again when dropped
+    pub(crate) struct TempDir(PathBuf);
+
+    impl TempDir {
+        /// `name` keeps tests running at the same time out of each other's way
+        pub(crate) fn new(name: &str) -> Self {
+            let path = std::env::temp_dir().join(format!("aoc2024-{name}-{}", std::process::id()));
+            let _ = std::fs::remove_dir_all(&path);
+            std::fs::create_dir_all(&path).unwrap();
+            TempDir(path)
+        }
+    }
+
+    impl std::ops::Deref for TempDir {
+        type Target = Path;
+
+        fn deref(&self) -> &Path {
+            &self.0
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    /// Pseudo-random numbers from a fixed seed (xorshift), so a generated test case is the same
+    /// every run
+    pub(crate) fn random() -> impl FnMut() -> u64 {
+        let mut state = 0x2545f4914f6cdd1d_u64;
+        move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        }
+    }
+
+    // Each part answers with the number of threads it was given
+    struct Budget;
+
+    impl Solution for Budget {
+        const DAY: u8 = 0;
+        type Input = ();
+        type Answer = usize;
+
+        fn parse(_: &str) -> Result<(), ParseError> {
+            Ok(())
+        }
+        fn part1(_: &()) -> usize {
+            parallel::available().get()
+        }
+        fn part2(_: &()) -> usize {
+            parallel::available().get()
+        }
+    }
+
+    #[test]
+    fn parts_share_the_threads() {
+        let day = Day::new::<Budget>();
+        let answers = |threads, part| {
+            let report = day
+                .run_parallel("", part, NonZeroUsize::new(threads).unwrap())
+                .unwrap();
+            (
+                report.answer(1).map(String::from),
+                report.answer(2).map(String::from),
+            )
+        };
+        let some = |n: usize| Some(n.to_string());
+        assert_eq!(answers(1, None), (some(1), some(1)));
+        assert_eq!(answers(5, None), (some(2), some(3)));
+        assert_eq!(answers(5, Some(2)), (None, some(5)));
+    }
+}
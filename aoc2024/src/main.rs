@@ -1,3 +1,430 @@
+/*
+ * Runner for every registered day. Instead of `cargo run --bin dayN` for each day:
+ *
+ *  cargo run -- 3 --part 2
+ *  cargo run -- all
+ *  cargo run -- all --example
+ *  cargo run -- 1 --input -
+ *  cargo run -- all --time --budget 1s
+ *  cargo run -- all --format json
+ *  cargo run -- all --jobs 1
+ *  cargo run -- verify
+ *  cargo run -- new-day 7
+ *  cargo run -- extract 7 ~/Downloads/day7.html
+ *  AOC_SESSION=... cargo run -- fetch 7
+ *  AOC_SESSION=... cargo run -- submit 7 1
+ */
+use std::num::NonZeroUsize;
+use std::time::Duration;
+
+use aoc2024::answers::{Answers, Source};
+use aoc2024::client::{self, Fetched};
+use aoc2024::days;
+use aoc2024::extract;
+use aoc2024::input::{InputArgs, input_path};
+use aoc2024::output::{self, Format, Record};
+use aoc2024::scaffold;
+use aoc2024::submit::{self, History, Submitted, Verdict};
+use aoc2024::{Day, Report, Timed, exit_with, parallel};
+use clap::{Parser, Subcommand};
+
+#[derive(Parser)]
+#[command(
+    about = "Run Advent of Code 2024 solutions",
+    args_conflicts_with_subcommands = true,
+    subcommand_negates_reqs = true
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[command(flatten)]
+    run: Args,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Re-run every day with answers in answers.toml and fail on any mismatch
+    Verify,
+    /// Download a day's puzzle input into data/N.input, unless it's already there
+    Fetch {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+    },
+    /// Submit an answer, refusing ones data/submissions.json already rules out
+    Submit {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        /// Submit this instead of running the day on data/N.input
+        answer: Option<String>,
+    },
+    /// Pull the example and its answers out of a saved puzzle page into data/ and answers.toml
+    Extract {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        /// The puzzle page, saved from the browser
+        page: std::path::PathBuf,
+    },
+    /// Create and register the files for a new day
+    NewDay {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+    },
+}
+
+#[derive(clap::Args)]
+struct Args {
+    /// Day to run, or `all` for every registered day
+    #[arg(required = true)]
+    day: Option<String>,
+
+    /// Only run this part (1 or 2)
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+
+    #[command(flatten)]
+    input: InputArgs,
+
+    /// Show how long parsing and each part took
+    #[arg(long)]
+    time: bool,
+
+    /// Flag any part slower than this, e.g. `1s` or `250ms` (implies --time)
+    #[arg(long, value_name = "DURATION", value_parser = parse_duration)]
+    budget: Option<Duration>,
+
+    /// Threads to use in all. Days run at once up to this many, and any to spare go to running
+    /// each day's parts side by side and to parts that work in parallel themselves. 1 runs
+    /// everything in order, which gives steadier timings. Defaults to the number of CPUs.
+    #[arg(short, long, value_name = "N")]
+    jobs: Option<NonZeroUsize>,
+
+    /// How to print results; json and csv give one record per part, with timings and input hash
+    #[arg(long, value_enum, default_value_t)]
+    format: Format,
+}
+
 fn main() {
-    println!("Hello, world!");
+    let cli = Cli::parse();
+    match cli.command {
+        Some(Command::Verify) => verify(),
+        Some(Command::Fetch { day }) => fetch(day),
+        Some(Command::Submit { day, part, answer }) => submit(day, part, answer),
+        Some(Command::Extract { day, page }) => extract(day, &page),
+        Some(Command::NewDay { day }) => new_day(day),
+        None => run(cli.run),
+    }
+}
+
+fn run(args: Args) {
+    let day = args.day.as_deref().unwrap_or("all");
+    let selected: Vec<&Day> = if day == "all" {
+        if args.input.is_explicit() {
+            exit_with("--input can only be used with a single day");
+        }
+        days::DAYS.iter().collect()
+    } else {
+        let number = match day.parse::<u8>() {
+            Ok(n) => n,
+            Err(_) => exit_with(format!("invalid day: {day}")),
+        };
+        match days::get(number) {
+            Some(d) => vec![d],
+            None => exit_with(format!("day {number} is not registered")),
+        }
+    };
+
+    let plain = args.format == Format::Plain;
+    let time = args.time || args.budget.is_some();
+
+    if plain {
+        print!("{:<5} {:<20} {:<20}", "Day", "Part1", "Part2");
+        if time {
+            print!(" {:>10} {:>10} {:>10}", "Parse", "Part1", "Part2");
+        }
+        println!();
+    }
+
+    // Inputs are read up front, stdin can only be read once
+    let inputs: Vec<(&Day, std::io::Result<String>)> = selected
+        .into_iter()
+        .map(|day| (day, args.input.read(day.number)))
+        .collect();
+    let jobs = args.jobs.unwrap_or_else(parallel::available);
+    let at_once = jobs.get().min(inputs.len()).max(1);
+    let per_day = NonZeroUsize::new(jobs.get() / at_once).unwrap();
+    let jobs = NonZeroUsize::new(at_once).unwrap();
+    let results = parallel::map(jobs, &inputs, |(day, data)| {
+        let data = data.as_ref().ok()?;
+        let report = day.run_parallel(data, args.part, per_day);
+        Some((report, output::input_hash(data)))
+    });
+
+    let mut reports = vec![];
+    let mut records = vec![];
+    let mut failures = 0;
+    for ((day, data), result) in inputs.iter().zip(results) {
+        let (report, input_hash) = match (data, result) {
+            (Ok(_), Some((Ok(report), input_hash))) => (report, input_hash),
+            (Err(e), _) if plain => {
+                println!("{:<5} couldn't read {e}", day.number);
+                failures += 1;
+                continue;
+            }
+            (Err(e), _) => {
+                eprintln!("error: day {}: couldn't read {e}", day.number);
+                failures += 1;
+                continue;
+            }
+            (Ok(_), Some((Err(e), _))) => {
+                if plain {
+                    println!("{:<5} couldn't parse input", day.number);
+                }
+                eprintln!("error: {}", e.in_file(args.input.name(day.number)));
+                failures += 1;
+                continue;
+            }
+            (Ok(_), None) => unreachable!("read inputs are always run"),
+        };
+
+        if !plain {
+            records.extend(Record::from_report(&report, &input_hash));
+            continue;
+        }
+        print!(
+            "{:<5} {:<20} {:<20}",
+            report.day,
+            report.answer(1).unwrap_or("-"),
+            report.answer(2).unwrap_or("-")
+        );
+        if time {
+            let elapsed = |p: &Option<Timed>| match p {
+                Some(t) => flag(t.elapsed, args.budget),
+                None => "-".to_string(),
+            };
+            print!(
+                " {:>10} {:>10} {:>10}",
+                format!("{:.2?}", report.parse),
+                elapsed(&report.part1),
+                elapsed(&report.part2)
+            );
+        }
+        println!();
+        reports.push(report);
+    }
+
+    match args.format {
+        Format::Plain if time => summary(&reports, args.budget),
+        Format::Plain => {}
+        Format::Json => println!("{}", output::json(&records)),
+        Format::Csv => print!("{}", output::csv(&records)),
+    }
+
+    // Still a failure for scripts reading the JSON or CSV, where a missing day is easy to miss
+    if failures > 0 {
+        exit_with(format!("couldn't run {failures} of {} days", inputs.len()));
+    }
+}
+
+fn summary(reports: &[Report], budget: Option<Duration>) {
+    let sum = |phase: fn(&Report) -> Duration| reports.iter().map(phase).sum::<Duration>();
+    println!(
+        "{:<47} {:>10} {:>10} {:>10}",
+        "Total",
+        format!("{:.2?}", sum(|r| r.parse)),
+        format!("{:.2?}", sum(|r| r.elapsed(1))),
+        format!("{:.2?}", sum(|r| r.elapsed(2)))
+    );
+    println!(
+        "{:<47} {:>10}",
+        "All phases",
+        format!("{:.2?}", sum(Report::total))
+    );
+
+    if let Some(budget) = budget {
+        let over = reports
+            .iter()
+            .flat_map(|r| [(r.day, 1, &r.part1), (r.day, 2, &r.part2)])
+            .filter(|(_, _, p)| p.as_ref().is_some_and(|t| t.elapsed > budget))
+            .map(|(day, part, _)| format!("day {day} part {part}"))
+            .collect::<Vec<_>>();
+        if !over.is_empty() {
+            println!("\n* over the {budget:.2?} budget: {}", over.join(", "));
+        }
+    }
+}
+
+fn verify() {
+    let answers = Answers::load(&Answers::path()).unwrap_or_else(|e| exit_with(e));
+
+    let mut failures = 0;
+    for (number, source) in answers.runs() {
+        let Some(day) = days::get(number) else {
+            println!("{number:<5} {source:<14} day is not registered");
+            failures += 1;
+            continue;
+        };
+
+        let path = source.path(number);
+        let data = match std::fs::read_to_string(&path) {
+            Ok(data) => data,
+            // Real inputs aren't checked in, so not having one isn't a failure
+            Err(_) if source == Source::Input => {
+                println!("{number:<5} {source:<14} skipped, no {}", path.display());
+                continue;
+            }
+            Err(e) => {
+                println!(
+                    "{number:<5} {source:<14} FAILED couldn't read {}: {e}",
+                    path.display()
+                );
+                failures += 1;
+                continue;
+            }
+        };
+
+        let report = match day.run(&data, answers.only_part(number, &source)) {
+            Ok(report) => report,
+            Err(e) => {
+                println!("{number:<5} {source:<14} FAILED couldn't parse input");
+                eprintln!("error: {}", e.in_file(path.display().to_string()));
+                failures += 1;
+                continue;
+            }
+        };
+
+        for part in [1, 2] {
+            let Some(expected) = answers.get(number, &source, part) else {
+                continue;
+            };
+            let actual = report.answer(part).unwrap_or_default();
+            if actual == expected {
+                println!("{number:<5} {source:<14} part{part} ok    {actual}");
+            } else {
+                println!("{number:<5} {source:<14} part{part} WRONG {actual}, expected {expected}");
+                failures += 1;
+            }
+        }
+    }
+
+    if failures > 0 {
+        exit_with(format!("{failures} answers don't match answers.toml"));
+    }
+}
+
+fn fetch(day: u8) {
+    let config = client::Config::load().unwrap_or_else(|e| exit_with(e));
+    let path = input_path(day);
+    match client::fetch_input(&config, day, &path) {
+        Ok(Fetched::Cached) => println!("{} is already there", path.display()),
+        Ok(Fetched::Downloaded) => println!("wrote {}", path.display()),
+        Err(e) => exit_with(e),
+    }
+}
+
+fn submit(day: u8, part: u8, answer: Option<String>) {
+    let answer = answer.unwrap_or_else(|| {
+        let Some(solution) = days::get(day) else {
+            exit_with(format!("day {day} is not registered"));
+        };
+        let input = InputArgs::default();
+        let data = input.read(day).unwrap_or_else(|e| exit_with(e));
+        let report = solution
+            .run(&data, Some(part))
+            .unwrap_or_else(|e| exit_with(e.in_file(input.name(day))));
+        let answer = report.answer(part).unwrap_or_default().to_string();
+        println!("Day {day} part {part}: {answer}");
+        answer
+    });
+
+    let config = client::Config::load().unwrap_or_else(|e| exit_with(e));
+    let mut history = History::load(&History::path()).unwrap_or_else(|e| exit_with(e));
+    match submit::submit(&config, &mut history, day, part, &answer) {
+        Ok(Submitted::Refused(reason)) => exit_with(format!("not submitting: {reason}")),
+        Ok(Submitted::Answered(outcome)) => {
+            let wait = outcome
+                .wait
+                .map(|wait| format!(", next attempt in {}s", wait.as_secs()))
+                .unwrap_or_default();
+            println!("{answer}: {}{wait}", outcome.verdict);
+            if outcome.verdict != Verdict::Right {
+                std::process::exit(1);
+            }
+        }
+        Err(e) => exit_with(e),
+    }
+}
+
+fn extract(day: u8, page: &std::path::Path) {
+    let root = std::path::Path::new(env!("CARGO_MANIFEST_DIR"));
+    let written = extract::extract(root, day, page).unwrap_or_else(|e| exit_with(e));
+    if written.is_empty() {
+        println!("nothing new, data/ and answers.toml already have it");
+    }
+    for path in written {
+        println!(
+            "wrote {}",
+            path.strip_prefix(root).unwrap_or(&path).display()
+        );
+    }
+}
+
+fn new_day(day: u8) {
+    let root = std::path::Path::new(env!("CARGO_MANIFEST_DIR"));
+    let written = scaffold::new_day(root, day).unwrap_or_else(|e| exit_with(e));
+    for path in written {
+        println!(
+            "wrote {}",
+            path.strip_prefix(root).unwrap_or(&path).display()
+        );
+    }
+    println!(
+        "\nPaste the example into data/{day}.example and its answers into answers.toml, or use \
+         `cargo run -- extract {day} PAGE`, then `cargo test` checks them"
+    );
+}
+
+// Mark a time that went over budget with `*`
+fn flag(elapsed: Duration, budget: Option<Duration>) -> String {
+    let marker = if budget.is_some_and(|b| elapsed > b) {
+        "*"
+    } else {
+        ""
+    };
+    format!("{marker}{elapsed:.2?}")
+}
+
+fn parse_duration(s: &str) -> Result<Duration, String> {
+    let split = s
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .ok_or_else(|| format!("missing a unit in `{s}`, e.g. 1s or 250ms"))?;
+    let (value, unit) = s.split_at(split);
+    let value = value
+        .parse::<f64>()
+        .map_err(|_| format!("invalid duration `{s}`"))?;
+    let seconds = match unit {
+        "s" => value,
+        "ms" => value / 1e3,
+        "us" | "µs" => value / 1e6,
+        "ns" => value / 1e9,
+        _ => return Err(format!("unknown unit `{unit}`, expected s, ms, us or ns")),
+    };
+    Duration::try_from_secs_f64(seconds).map_err(|_| format!("`{s}` is too long"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn durations() {
+        assert_eq!(parse_duration("1s"), Ok(Duration::from_secs(1)));
+        assert_eq!(parse_duration("250ms"), Ok(Duration::from_millis(250)));
+        assert_eq!(parse_duration("1.5s"), Ok(Duration::from_millis(1500)));
+        assert!(parse_duration("10").is_err());
+        assert!(parse_duration("10m").is_err());
+        assert!(parse_duration("99999999999999999999999s").is_err());
+    }
 }
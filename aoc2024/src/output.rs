@@ -0,0 +1,137 @@
+/*
+ * Machine-readable runner output: one record per day and part, as JSON or CSV.
+ *
+ * Times are whole nanoseconds. `input_hash` is FNV-1a over the input bytes, so results from the
+ * same input can be grouped without shipping the input itself.
+ */
+use std::fmt::Write;
+
+use serde::Serialize;
+
+use crate::Report;
+
+#[derive(clap::ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Format {
+    /// The table, for people
+    #[default]
+    Plain,
+    Json,
+    Csv,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Record {
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub parse_ns: u64,
+    pub elapsed_ns: u64,
+    pub input_hash: String,
+}
+
+impl Record {
+    /// One record for each part that was run
+    pub fn from_report(report: &Report, input_hash: &str) -> Vec<Record> {
+        [(1, &report.part1), (2, &report.part2)]
+            .into_iter()
+            .filter_map(|(part, timed)| {
+                let timed = timed.as_ref()?;
+                Some(Record {
+                    day: report.day,
+                    part,
+                    answer: timed.answer.clone(),
+                    parse_ns: report.parse.as_nanos() as u64,
+                    elapsed_ns: timed.elapsed.as_nanos() as u64,
+                    input_hash: input_hash.to_string(),
+                })
+            })
+            .collect()
+    }
+}
+
+/// 64-bit FNV-1a of `data`, as 16 hex digits
+pub fn input_hash(data: &str) -> String {
+    let hash = data.bytes().fold(0xcbf29ce484222325_u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    });
+    format!("{hash:016x}")
+}
+
+/// A JSON array of records
+pub fn json(records: &[Record]) -> String {
+    serde_json::to_string_pretty(records).expect("records always serialize")
+}
+
+/// CSV with a header row
+pub fn csv(records: &[Record]) -> String {
+    let mut out = String::from("day,part,answer,parse_ns,elapsed_ns,input_hash\n");
+    for r in records {
+        writeln!(
+            out,
+            "{},{},{},{},{},{}",
+            r.day,
+            r.part,
+            csv_field(&r.answer),
+            r.parse_ns,
+            r.elapsed_ns,
+            r.input_hash
+        )
+        .unwrap();
+    }
+    out
+}
+
+// Quote a field if it would otherwise break the row
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::Timed;
+
+    fn records() -> Vec<Record> {
+        let report = Report {
+            day: 3,
+            parse: Duration::from_micros(5),
+            part1: Some(Timed {
+                answer: "161".to_string(),
+                elapsed: Duration::from_nanos(1200),
+            }),
+            part2: None,
+        };
+        Record::from_report(&report, &input_hash("mul(2,4)"))
+    }
+
+    #[test]
+    fn hash() {
+        // Published FNV-1a test vectors
+        assert_eq!(input_hash(""), "cbf29ce484222325");
+        assert_eq!(input_hash("a"), "af63dc4c8601ec8c");
+    }
+
+    #[test]
+    fn formats() {
+        let records = records();
+        assert_eq!(records.len(), 1);
+
+        let json: serde_json::Value = serde_json::from_str(&json(&records)).unwrap();
+        assert_eq!(json[0]["answer"], "161");
+        assert_eq!(json[0]["parse_ns"], 5000);
+
+        let csv = csv(&records);
+        let hash = input_hash("mul(2,4)");
+        assert_eq!(
+            csv,
+            format!("day,part,answer,parse_ns,elapsed_ns,input_hash\n3,1,161,5000,1200,{hash}\n")
+        );
+        assert_eq!(csv_field("a,\"b\""), "\"a,\"\"b\"\"\"");
+    }
+}
@@ -0,0 +1,105 @@
+/*
+ * Just enough of a thread pool: run a function over a slice on a fixed number of scoped threads,
+ * handing out items one at a time so a slow one doesn't hold up a whole batch.
+ */
+use std::cell::Cell;
+use std::num::NonZeroUsize;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+thread_local! {
+    static BUDGET: Cell<Option<NonZeroUsize>> = const { Cell::new(None) };
+}
+
+/// How many threads to use from here: the budget set by [`with_budget`] if inside one, otherwise
+/// as many as the machine can usefully run. At least 1.
+pub fn available() -> NonZeroUsize {
+    BUDGET
+        .get()
+        .unwrap_or_else(|| std::thread::available_parallelism().unwrap_or(NonZeroUsize::MIN))
+}
+
+/// Run `f` with [`available`] saying `threads` on this thread, so work it starts doesn't take
+/// more than its share
+pub fn with_budget<R>(threads: NonZeroUsize, f: impl FnOnce() -> R) -> R {
+    let outer = BUDGET.replace(Some(threads));
+    let result = f();
+    BUDGET.set(outer);
+    result
+}
+
+/// `f` over every item using up to `threads` threads, with the results in the same order as
+/// `items`. With one thread (or one item) everything runs on the calling thread.
+pub fn map<T, R>(threads: NonZeroUsize, items: &[T], f: impl Fn(&T) -> R + Sync) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+{
+    let threads = threads.get().min(items.len());
+    if threads <= 1 {
+        return items.iter().map(f).collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<R>>> = Mutex::new(items.iter().map(|_| None).collect());
+    std::thread::scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(|| {
+                loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(item) = items.get(i) else {
+                        break;
+                    };
+                    let result = f(item);
+                    results.lock().unwrap()[i] = Some(result);
+                }
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|r| r.expect("every item is mapped before the scope ends"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_order() {
+        let items: Vec<u64> = (0..100).collect();
+        let threads = NonZeroUsize::new(8).unwrap();
+        // Early items are the slowest, so they finish last
+        let squares = map(threads, &items, |&n| {
+            std::thread::sleep(std::time::Duration::from_micros(100 - n));
+            n * n
+        });
+        assert_eq!(squares, items.iter().map(|n| n * n).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn budget() {
+        let two = NonZeroUsize::new(2).unwrap();
+        let inner = with_budget(two, || {
+            (
+                available(),
+                with_budget(NonZeroUsize::MIN, available),
+                available(),
+            )
+        });
+        assert_eq!(inner, (two, NonZeroUsize::MIN, two));
+        // Only for this thread
+        let other = with_budget(two, || std::thread::spawn(available).join().unwrap());
+        assert_eq!(other, available());
+    }
+
+    #[test]
+    fn serial_and_empty() {
+        assert_eq!(map(NonZeroUsize::MIN, &[1, 2, 3], |n| n + 1), [2, 3, 4]);
+        assert!(map(available(), &[] as &[u8], |n| *n).is_empty());
+    }
+}
@@ -0,0 +1,208 @@
+/*
+ * 2D template matching: find every place a small pattern fits in a grid. Cells of the pattern
+ * can be wildcards, which match anything, and the pattern can also be tried in each of its
+ * rotations and reflections.
+ *
+ *  M.S
+ *  .A.    with `.` as the wildcard is day4 part2's MAS cross
+ *  M.S
+ */
+use crate::error::ParseError;
+use crate::geometry::Point;
+use crate::grid::Grid;
+
+/// A rectangle of cells to look for, where `None` matches anything
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Pattern<T> {
+    cells: Grid<Option<T>>,
+}
+
+impl Pattern<char> {
+    /// One row per line, with `wildcard` for cells that match anything
+    pub fn parse(text: &str, wildcard: char) -> Result<Self, ParseError> {
+        let cells = Grid::parse(text, |c| Some((c != wildcard).then_some(c)))?;
+        Ok(Pattern { cells })
+    }
+}
+
+impl<T: Clone + PartialEq> Pattern<T> {
+    pub fn new(cells: Grid<Option<T>>) -> Self {
+        Pattern { cells }
+    }
+
+    pub fn height(&self) -> usize {
+        self.cells.height()
+    }
+
+    pub fn width(&self) -> usize {
+        self.cells.width()
+    }
+
+    /// A quarter turn clockwise
+    pub fn rotate(&self) -> Self {
+        let (height, width) = (self.height(), self.width());
+        let mut cells = Grid::new(width, height, None);
+        for (p, cell) in self.cells.iter() {
+            cells.set(Point::new(p.y, (height - 1) as i32 - p.x), cell.clone());
+        }
+        Pattern { cells }
+    }
+
+    /// Mirrored left to right
+    pub fn flip(&self) -> Self {
+        let width = self.width() as i32;
+        let mut cells = self.cells.clone();
+        for (p, cell) in self.cells.iter() {
+            cells.set(Point::new(p.x, width - 1 - p.y), cell.clone());
+        }
+        Pattern { cells }
+    }
+
+    /// The pattern in all 4 rotations, then those mirrored, leaving out any that come out the
+    /// same as one before
+    pub fn symmetries(&self) -> Vec<Self> {
+        let mut all: Vec<Self> = vec![];
+        for mut pattern in [self.clone(), self.flip()] {
+            for _ in 0..4 {
+                let next = pattern.rotate();
+                if !all.contains(&pattern) {
+                    all.push(pattern);
+                }
+                pattern = next;
+            }
+        }
+        all
+    }
+
+    /// Whether the pattern fits with its top left corner at `top_left`
+    pub fn matches_at(&self, grid: &Grid<T>, top_left: Point) -> bool {
+        self.cells.iter().all(|(p, cell)| match cell {
+            None => grid.contains(top_left + p),
+            Some(want) => grid.get(top_left + p) == Some(want),
+        })
+    }
+
+    /// The grid cells the pattern's non-wildcard cells cover when placed at `top_left`
+    pub fn cells(&self, top_left: Point) -> impl Iterator<Item = Point> + '_ {
+        self.cells
+            .iter()
+            .filter(|(_, cell)| cell.is_some())
+            .map(move |(p, _)| top_left + p)
+    }
+}
+
+/// Where a [`Matcher`] found its pattern
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Placement {
+    pub top_left: Point,
+    /// Index into [`Matcher::variants`]
+    pub variant: usize,
+}
+
+/// Looks for one pattern, or any of a set of variants of it
+#[derive(Debug, Clone)]
+pub struct Matcher<T> {
+    variants: Vec<Pattern<T>>,
+}
+
+impl<T: Clone + PartialEq> Matcher<T> {
+    /// Just `pattern`, the way round it's given
+    pub fn new(pattern: Pattern<T>) -> Self {
+        Matcher {
+            variants: vec![pattern],
+        }
+    }
+
+    /// `pattern` in any rotation or reflection. Those that look the same are only tried once,
+    /// so a symmetric shape isn't counted more than once in the same place.
+    pub fn symmetric(pattern: Pattern<T>) -> Self {
+        Matcher {
+            variants: pattern.symmetries(),
+        }
+    }
+
+    pub fn variants(&self) -> &[Pattern<T>] {
+        &self.variants
+    }
+
+    /// Every placement of every variant, in reading order of the top left corner
+    pub fn find(&self, grid: &Grid<T>) -> Vec<Placement> {
+        let mut found = vec![];
+        for x in 0..grid.height() {
+            for y in 0..grid.width() {
+                let top_left = Point::new(x as i32, y as i32);
+                for (variant, pattern) in self.variants.iter().enumerate() {
+                    if pattern.matches_at(grid, top_left) {
+                        found.push(Placement { top_left, variant });
+                    }
+                }
+            }
+        }
+        found
+    }
+
+    pub fn count(&self, grid: &Grid<T>) -> usize {
+        self.find(grid).len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cross() -> Pattern<char> {
+        Pattern::parse("M.S\n.A.\nM.S\n", '.').unwrap()
+    }
+
+    #[test]
+    fn rotations_and_reflections() {
+        let l = Pattern::parse("ab\nc.\ne.\n", '.').unwrap();
+        assert_eq!(l.rotate(), Pattern::parse("eca\n..b\n", '.').unwrap());
+        assert_eq!(l.flip(), Pattern::parse("ba\n.c\n.e\n", '.').unwrap());
+        assert_eq!(l.rotate().rotate().rotate().rotate(), l);
+        assert_eq!(l.symmetries().len(), 8);
+
+        // The MAS cross is the same flipped as rotated twice, so it has 4 distinct ways round
+        assert_eq!(cross().symmetries().len(), 4);
+        let square = Pattern::parse("ab\nba\n", '.').unwrap();
+        assert_eq!(square.symmetries().len(), 2);
+    }
+
+    #[test]
+    fn finds_placements() {
+        let grid = Grid::parse("MXSSXS\nXAXXAX\nMXSMXM\n", Some).unwrap();
+        let exact = Matcher::new(cross());
+        assert_eq!(
+            exact.find(&grid),
+            [Placement {
+                top_left: Point::new(0, 0),
+                variant: 0
+            }]
+        );
+
+        // The second is S.S/.A./M.M, the cross rotated
+        let any = Matcher::symmetric(cross());
+        let found = any.find(&grid);
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[1].top_left, Point::new(0, 3));
+        let variant = &any.variants()[found[1].variant];
+        assert_eq!(variant, &Pattern::parse("S.S\n.A.\nM.M\n", '.').unwrap());
+        assert_eq!(
+            variant.cells(found[1].top_left).collect::<Vec<_>>(),
+            [
+                Point::new(0, 3),
+                Point::new(0, 5),
+                Point::new(1, 4),
+                Point::new(2, 3),
+                Point::new(2, 5)
+            ]
+        );
+    }
+
+    #[test]
+    fn wildcards_stay_on_the_grid() {
+        let grid = Grid::parse("ab\ncd\n", Some).unwrap();
+        let pattern = Pattern::parse("d.\n", '.').unwrap();
+        assert_eq!(Matcher::new(pattern).count(&grid), 0);
+    }
+}
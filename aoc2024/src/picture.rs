@@ -0,0 +1,282 @@
+/*
+ * Grids as images, for sharing: a PNG of one state, or an animated GIF of a simulation with one
+ * frame per step. Each cell is drawn as a `scale` by `scale` square of one colour, which the day
+ * picks from what's in the cell.
+ *
+ * GIF frames after the first only hold the rectangle that changed since the one before, so a long
+ * walk where a couple of cells change each step stays small.
+ */
+use std::borrow::Cow;
+use std::io::{self, Write};
+use std::num::{NonZeroU32, NonZeroUsize};
+
+use crate::geometry::Point;
+use crate::grid::Grid;
+
+pub type Rgb = [u8; 3];
+
+/// A `Grid<Rgb>` from any grid, colouring each cell with `colour`
+pub fn paint<T>(grid: &Grid<T>, colour: impl Fn(Point, &T) -> Rgb) -> Grid<Rgb> {
+    let mut image = Grid::new(grid.height(), grid.width(), [0; 3]);
+    for (p, cell) in grid.iter() {
+        image.set(p, colour(p, cell));
+    }
+    image
+}
+
+/// Write `image` as a PNG
+pub fn write_png(out: impl Write, image: &Grid<Rgb>, scale: NonZeroUsize) -> io::Result<()> {
+    let scale = scale.get();
+    let (height, width) = (image.height() * scale, image.width() * scale);
+    let too_big = || io::Error::other(format!("{height}x{width} is too big for a PNG"));
+    let mut encoder = png::Encoder::new(
+        out,
+        width.try_into().map_err(|_| too_big())?,
+        height.try_into().map_err(|_| too_big())?,
+    );
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+
+    let pixels = scaled(image, (0, 0), (image.height(), image.width()), scale);
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(pixels.as_flattened())?;
+    Ok(writer.finish()?)
+}
+
+/// An animated GIF, written a frame at a time. It loops, holding the last frame for a moment
+/// before starting again.
+pub struct Gif<W: Write> {
+    encoder: gif::Encoder<W>,
+    scale: usize,
+    delay: u16,
+    previous: Option<Grid<Rgb>>,
+    // Written once we know whether it's the last
+    pending: Option<gif::Frame<'static>>,
+}
+
+impl<W: Write> Gif<W> {
+    /// For frames of `height` by `width` cells, shown `fps` a second. GIF delays are whole
+    /// hundredths of a second and viewers slow down anything under two, so 50 is the fastest.
+    pub fn new(
+        out: W,
+        height: usize,
+        width: usize,
+        scale: NonZeroUsize,
+        fps: NonZeroU32,
+    ) -> io::Result<Self> {
+        let scale = scale.get();
+        let too_big = || {
+            io::Error::other(format!(
+                "{}x{} is too big for a GIF",
+                height * scale,
+                width * scale
+            ))
+        };
+        let mut encoder = gif::Encoder::new(
+            out,
+            (width * scale).try_into().map_err(|_| too_big())?,
+            (height * scale).try_into().map_err(|_| too_big())?,
+            &[],
+        )
+        .map_err(gif_error)?;
+        encoder
+            .set_repeat(gif::Repeat::Infinite)
+            .map_err(gif_error)?;
+
+        Ok(Gif {
+            encoder,
+            scale,
+            delay: (100 / fps.get()).clamp(2, u16::MAX as u32) as u16,
+            previous: None,
+            pending: None,
+        })
+    }
+
+    /// Add the next frame, which must be the size given to [`Gif::new`]
+    pub fn frame(&mut self, image: &Grid<Rgb>) -> io::Result<()> {
+        // Only what changed is drawn over the frame before. If nothing did, one cell stands in,
+        // since the frame still takes up time.
+        let ((top, left), (bottom, right)) = match &self.previous {
+            None => ((0, 0), (image.height() - 1, image.width() - 1)),
+            Some(previous) => {
+                assert_eq!(
+                    (previous.height(), previous.width()),
+                    (image.height(), image.width()),
+                    "every frame is the same size"
+                );
+                changed(previous, image).unwrap_or(((0, 0), (0, 0)))
+            }
+        };
+        let size = (bottom - top + 1, right - left + 1);
+        let pixels = scaled(image, (top, left), size, self.scale);
+
+        let mut palette: Vec<Rgb> = vec![];
+        let mut indices = Vec::with_capacity(pixels.len());
+        for pixel in pixels {
+            let index = match palette.iter().position(|&c| c == pixel) {
+                Some(index) => index,
+                None => {
+                    palette.push(pixel);
+                    palette.len() - 1
+                }
+            };
+            if index > u8::MAX as usize {
+                return Err(io::Error::other("more than 256 colours in one GIF frame"));
+            }
+            indices.push(index as u8);
+        }
+
+        let frame = gif::Frame {
+            delay: self.delay,
+            dispose: gif::DisposalMethod::Keep,
+            top: (top * self.scale) as u16,
+            left: (left * self.scale) as u16,
+            height: (size.0 * self.scale) as u16,
+            width: (size.1 * self.scale) as u16,
+            palette: Some(palette.as_flattened().to_vec()),
+            buffer: Cow::Owned(indices),
+            ..gif::Frame::default()
+        };
+        if let Some(pending) = self.pending.replace(frame) {
+            self.encoder.write_frame(&pending).map_err(gif_error)?;
+        }
+        self.previous = Some(image.clone());
+        Ok(())
+    }
+
+    /// Write the last frame, flush and hand back the writer
+    pub fn finish(mut self) -> io::Result<W> {
+        if let Some(mut last) = self.pending.take() {
+            last.delay = last.delay.max(200);
+            self.encoder.write_frame(&last).map_err(gif_error)?;
+        }
+        let mut out = self.encoder.into_inner().map_err(gif_error)?;
+        out.flush()?;
+        Ok(out)
+    }
+}
+
+// Cells `top_left` onwards, `size` (height, width) of them, as rows of pixels
+fn scaled(
+    image: &Grid<Rgb>,
+    (top, left): (usize, usize),
+    (height, width): (usize, usize),
+    scale: usize,
+) -> Vec<Rgb> {
+    let mut pixels = Vec::with_capacity(height * width * scale * scale);
+    for x in top..top + height {
+        let row = &image.row(x)[left..left + width];
+        for _ in 0..scale {
+            for &colour in row {
+                pixels.extend(std::iter::repeat_n(colour, scale));
+            }
+        }
+    }
+    pixels
+}
+
+// The top left and bottom right cells of the smallest rectangle holding every difference
+fn changed(a: &Grid<Rgb>, b: &Grid<Rgb>) -> Option<((usize, usize), (usize, usize))> {
+    let mut bounds: Option<((usize, usize), (usize, usize))> = None;
+    for (p, colour) in b.iter() {
+        if a.get(p) == Some(colour) {
+            continue;
+        }
+        let (x, y) = (p.x as usize, p.y as usize);
+        bounds = Some(match bounds {
+            None => ((x, y), (x, y)),
+            Some(((top, left), (bottom, right))) => {
+                ((top.min(x), left.min(y)), (bottom.max(x), right.max(y)))
+            }
+        });
+    }
+    bounds
+}
+
+fn gif_error(e: gif::EncodingError) -> io::Error {
+    match e {
+        gif::EncodingError::Io(e) => e,
+        e => io::Error::other(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BLACK: Rgb = [0, 0, 0];
+    const RED: Rgb = [255, 0, 0];
+
+    fn image(cells: &str) -> Grid<Rgb> {
+        let grid = Grid::parse(cells, Some).unwrap();
+        paint(&grid, |_, &c| if c == '#' { RED } else { BLACK })
+    }
+
+    #[test]
+    fn png() {
+        let mut out = vec![];
+        let scale = NonZeroUsize::new(2).unwrap();
+        write_png(&mut out, &image("#..\n..#\n"), scale).unwrap();
+
+        let mut reader = png::Decoder::new(io::Cursor::new(out)).read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size().unwrap()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+        assert_eq!((info.width, info.height), (6, 4));
+        let pixel = |x: usize, y: usize| &pixels[(x * 6 + y) * 3..][..3];
+        assert_eq!(pixel(1, 1), RED);
+        assert_eq!(pixel(1, 2), BLACK);
+        assert_eq!(pixel(3, 5), RED);
+    }
+
+    #[test]
+    fn gif_frames_hold_changes() {
+        let frames = [
+            ".....\n.....\n",
+            ".#...\n.....\n",
+            ".#...\n...#.\n",
+            ".#...\n...#.\n",
+        ];
+        let mut gif = Gif::new(
+            vec![],
+            2,
+            5,
+            NonZeroUsize::MIN,
+            NonZeroU32::new(10).unwrap(),
+        )
+        .unwrap();
+        for frame in frames {
+            gif.frame(&image(frame)).unwrap();
+        }
+        let out = gif.finish().unwrap();
+
+        let mut decoder = gif::DecodeOptions::new()
+            .read_info(io::Cursor::new(out))
+            .unwrap();
+        let mut regions = vec![];
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            regions.push((
+                frame.top,
+                frame.left,
+                frame.height,
+                frame.width,
+                frame.delay,
+            ));
+        }
+        assert_eq!(
+            regions,
+            [
+                (0, 0, 2, 5, 10),
+                (0, 1, 1, 1, 10),
+                (1, 3, 1, 1, 10),
+                // Nothing changed, and the last frame is held
+                (0, 0, 1, 1, 200)
+            ]
+        );
+
+        // Two changes far apart make one rectangle around both
+        assert_eq!(
+            changed(&image("#....\n.....\n"), &image(".....\n....#\n")),
+            Some(((0, 0), (1, 4)))
+        );
+    }
+}
@@ -0,0 +1,198 @@
+/*
+ * `new-day N`: everything a new day needs, so nothing has to be copied from the previous one.
+ *
+ *  src/days/dayN.rs   the solution
+ *  src/bin/dayN.rs    its standalone binary
+ *  data/N.example     empty, for pasting the puzzle's example into
+ *
+ * and the day is registered in src/days/mod.rs so the runner picks it up. Once the example has
+ * answers in answers.toml (see `extract`), the example tests in src/days/mod.rs check it.
+ */
+use std::path::{Path, PathBuf};
+
+use crate::Error;
+
+const SOLUTION: &str = r#"/*
+ * Part1:
+ *
+ * Part2:
+ */
+use crate::Solution;
+use crate::error::ParseError;
+
+pub struct DayN;
+
+impl Solution for DayN {
+    const DAY: u8 = N;
+
+    type Input = Vec<String>;
+    type Answer = u64;
+
+    fn parse(data: &str) -> Result<Self::Input, ParseError> {
+        if data.trim().is_empty() {
+            return Err(ParseError::empty());
+        }
+        Ok(data.lines().map(String::from).collect())
+    }
+
+    fn part1(_input: &Self::Input) -> u64 {
+        0
+    }
+
+    fn part2(_input: &Self::Input) -> u64 {
+        0
+    }
+}
+"#;
+
+const BINARY: &str = r#"use aoc2024::days::dayN::DayN;
+use aoc2024::input::InputArgs;
+use aoc2024::{Solution, exit_with};
+use clap::Parser;
+
+fn main() {
+    let input = InputArgs::parse()
+        .load::<DayN>()
+        .unwrap_or_else(|e| exit_with(e));
+    let part1 = DayN::part1(&input);
+    println!("Part1: {part1}");
+    let part2 = DayN::part2(&input);
+    println!("Part2: {part2}");
+}
+"#;
+
+/// Create and register day `day` under the crate at `root`, returning the files written.
+/// Nothing is overwritten: if the day already exists this fails before touching anything.
+pub fn new_day(root: &Path, day: u8) -> Result<Vec<PathBuf>, Error> {
+    if !(1..=25).contains(&day) {
+        return Err(Error::Config(format!("day {day} isn't between 1 and 25")));
+    }
+
+    let fill = |template: &str| {
+        template
+            .replace("DayN", &format!("Day{day}"))
+            .replace("dayN", &format!("day{day}"))
+            .replace("u8 = N;", &format!("u8 = {day};"))
+    };
+    let files = [
+        (root.join(format!("src/days/day{day}.rs")), fill(SOLUTION)),
+        (root.join(format!("src/bin/day{day}.rs")), fill(BINARY)),
+        (root.join(format!("data/{day}.example")), String::new()),
+    ];
+    for (path, _) in &files {
+        if path.exists() {
+            return Err(Error::Config(format!("{} already exists", path.display())));
+        }
+    }
+
+    let registry = root.join("src/days/mod.rs");
+    let registered = register(&std::fs::read_to_string(&registry)?, day)
+        .map_err(|e| Error::Config(format!("{e} in {}", registry.display())))?;
+
+    for (path, contents) in &files {
+        std::fs::write(path, contents)?;
+    }
+    std::fs::write(&registry, registered)?;
+
+    Ok(files
+        .into_iter()
+        .map(|(path, _)| path)
+        .chain([registry])
+        .collect())
+}
+
+// Add `pub mod dayN;` and `Day::new::<dayN::DayN>()` to `DAYS`, each among the other days in
+// order. Fails if the day is already there or the file doesn't look like we expect.
+fn register(registry: &str, day: u8) -> Result<String, String> {
+    let mut lines: Vec<String> = registry.lines().map(String::from).collect();
+    let entry = format!("    Day::new::<day{day}::Day{day}>(),");
+    if lines.contains(&entry) {
+        return Err(format!("day {day} is already registered"));
+    }
+    let unexpected = || "couldn't find the day list".to_string();
+
+    let mods: Vec<usize> = (0..lines.len())
+        .filter(|&i| lines[i].starts_with("pub mod day"))
+        .collect();
+    let at = insert_at(&lines, &mods, day, "pub mod day").ok_or_else(unexpected)?;
+    lines.insert(at, format!("pub mod day{day};"));
+
+    let days_start = lines
+        .iter()
+        .position(|l| l.starts_with("pub const DAYS"))
+        .ok_or_else(unexpected)?;
+    let days_end = days_start
+        + lines[days_start..]
+            .iter()
+            .position(|l| l == "];")
+            .ok_or_else(unexpected)?;
+    let entries: Vec<usize> = (days_start + 1..days_end).collect();
+    let at = insert_at(&lines, &entries, day, "    Day::new::<day").unwrap_or(days_end);
+    lines.insert(at, entry);
+
+    Ok(lines.join("\n") + "\n")
+}
+
+// Where day `day` goes among `candidates` (line indices, in order), whose lines name a day
+// right after `prefix`: before the first later day, otherwise after the last one. `None` if
+// there are no candidates.
+fn insert_at(lines: &[String], candidates: &[usize], day: u8, prefix: &str) -> Option<usize> {
+    let number = |i: usize| -> Option<u8> {
+        let rest = lines[i].strip_prefix(prefix)?;
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        rest[..digits].parse().ok()
+    };
+    let later = candidates
+        .iter()
+        .find(|&&i| number(i).is_some_and(|n| n > day));
+    match later {
+        Some(&i) => Some(i),
+        None => candidates.last().map(|&i| i + 1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::TempDir;
+
+    #[test]
+    fn registers_day() {
+        let registry = include_str!("days/mod.rs");
+        let updated = register(registry, 25).unwrap();
+        assert!(updated.contains("pub mod day25;\n"));
+        assert!(updated.contains("    Day::new::<day25::Day25>(),\n];"));
+        assert_eq!(updated.lines().count(), registry.lines().count() + 2);
+
+        // In order among the others, and only once
+        assert_eq!(
+            register(registry, 3).unwrap_err(),
+            "day 3 is already registered"
+        );
+        let registry = registry
+            .replace("pub mod day3;\n", "")
+            .replace("    Day::new::<day3::Day3>(),\n", "");
+        let updated = register(&registry, 3).unwrap();
+        assert_eq!(updated, include_str!("days/mod.rs"));
+    }
+
+    #[test]
+    fn creates_files() {
+        let root = TempDir::new("scaffold");
+        for dir in ["src/days", "src/bin", "data"] {
+            std::fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        std::fs::write(root.join("src/days/mod.rs"), include_str!("days/mod.rs")).unwrap();
+
+        let written = new_day(&root, 25).unwrap();
+        assert_eq!(written.len(), 4);
+        let solution = std::fs::read_to_string(root.join("src/days/day25.rs")).unwrap();
+        assert!(solution.contains("impl Solution for Day25 {\n    const DAY: u8 = 25;"));
+        assert!(root.join("data/25.example").exists());
+
+        // A second run must not clobber anything
+        assert!(new_day(&root, 25).is_err());
+    }
+}
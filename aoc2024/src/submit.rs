@@ -0,0 +1,348 @@
+/*
+ * `submit N P`: post an answer and remember what adventofcode.com said about it.
+ *
+ * Every attempt goes into `data/submissions.json`. Before posting, that history is checked so we
+ * never spend a guess (and a growing timeout) on something already known to be wrong:
+ *
+ *  - the part is already solved
+ *  - the same answer was rejected before
+ *  - a numeric answer at or above one that was "too high", or at or below one that was "too low"
+ *  - the site asked us to wait and that time hasn't passed yet
+ */
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::Error;
+use crate::client::{Client, Config};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Right,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// Submitted too soon after the previous attempt, the answer wasn't checked
+    Wait,
+}
+
+impl Verdict {
+    /// The answer was checked and isn't it
+    pub fn is_rejection(self) -> bool {
+        matches!(self, Verdict::Wrong | Verdict::TooHigh | Verdict::TooLow)
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Verdict::Right => "right",
+            Verdict::Wrong => "wrong",
+            Verdict::TooHigh => "too high",
+            Verdict::TooLow => "too low",
+            Verdict::Wait => "wait",
+        })
+    }
+}
+
+/// What the answer page said
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Outcome {
+    pub verdict: Verdict,
+    /// How long before the next attempt is allowed, if the page said
+    pub wait: Option<Duration>,
+}
+
+static LEFT_TO_WAIT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait").unwrap());
+static WAIT_MINUTES: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"wait (one|\d+) minutes? before trying again").unwrap());
+
+/// Read the verdict out of the page returned after posting an answer
+pub fn parse_outcome(page: &str) -> Result<Outcome, Error> {
+    let verdict = if page.contains("That's the right answer") {
+        Verdict::Right
+    } else if page.contains("You gave an answer too recently") {
+        Verdict::Wait
+    } else if page.contains("your answer is too high") {
+        Verdict::TooHigh
+    } else if page.contains("your answer is too low") {
+        Verdict::TooLow
+    } else if page.contains("That's not the right answer") {
+        Verdict::Wrong
+    } else if page.contains("You don't seem to be solving the right level") {
+        return Err(Error::Http(
+            "that part isn't open for answers, is it already solved?".to_string(),
+        ));
+    } else {
+        return Err(Error::Http(
+            "couldn't find a verdict in the answer page".to_string(),
+        ));
+    };
+
+    let wait = if let Some(caps) = LEFT_TO_WAIT.captures(page) {
+        let minutes: u64 = caps.get(1).map_or(0, |m| m.as_str().parse().unwrap());
+        let seconds: u64 = caps[2].parse().unwrap();
+        Some(Duration::from_secs(minutes * 60 + seconds))
+    } else {
+        WAIT_MINUTES.captures(page).map(|caps| {
+            let minutes = match &caps[1] {
+                "one" => 1,
+                n => n.parse().unwrap(),
+            };
+            Duration::from_secs(minutes * 60)
+        })
+    };
+
+    Ok(Outcome { verdict, wait })
+}
+
+/// One posted answer, as kept in the history
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attempt {
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Seconds since the Unix epoch
+    pub time: u64,
+    /// No further attempt on this day before this time, in seconds since the Unix epoch
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wait_until: Option<u64>,
+}
+
+/// Every answer ever submitted, kept in a JSON file
+#[derive(Debug, Clone, Default)]
+pub struct History {
+    path: PathBuf,
+    attempts: Vec<Attempt>,
+}
+
+impl History {
+    /// `data/submissions.json`
+    pub fn path() -> PathBuf {
+        crate::input::data_dir().join("submissions.json")
+    }
+
+    /// Load the history at `path`; a missing file is an empty history
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let attempts = match std::fs::read_to_string(path) {
+            Ok(text) => serde_json::from_str(&text)
+                .map_err(|e| Error::Config(format!("{}: {e}", path.display())))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => vec![],
+            Err(e) => return Err(e.into()),
+        };
+        Ok(History {
+            path: path.to_path_buf(),
+            attempts,
+        })
+    }
+
+    pub fn attempts(&self) -> &[Attempt] {
+        &self.attempts
+    }
+
+    /// Why `answer` shouldn't be submitted for `day`/`part` at time `now`, if it shouldn't
+    pub fn refusal(&self, day: u8, part: u8, answer: &str, now: u64) -> Option<String> {
+        let same_day = || self.attempts.iter().filter(move |a| a.day == day);
+        let same_part = || same_day().filter(move |a| a.part == part);
+
+        if let Some(right) = same_part().find(|a| a.verdict == Verdict::Right) {
+            return Some(format!(
+                "day {day} part {part} is already solved with {}",
+                right.answer
+            ));
+        }
+        if let Some(seen) = same_part().find(|a| a.answer == answer && a.verdict.is_rejection()) {
+            return Some(format!("{answer} was already {}", seen.verdict));
+        }
+
+        if let Ok(n) = answer.parse::<i128>() {
+            let bound = |verdict| {
+                same_part()
+                    .filter(move |a| a.verdict == verdict)
+                    .filter_map(|a| a.answer.parse::<i128>().ok())
+            };
+            if let Some(high) = bound(Verdict::TooHigh).min().filter(|&high| n >= high) {
+                return Some(format!(
+                    "{answer} can't be right, {high} was already too high"
+                ));
+            }
+            if let Some(low) = bound(Verdict::TooLow).max().filter(|&low| n <= low) {
+                return Some(format!(
+                    "{answer} can't be right, {low} was already too low"
+                ));
+            }
+        }
+
+        let wait_until = same_day().filter_map(|a| a.wait_until).max();
+        if let Some(until) = wait_until.filter(|&until| until > now) {
+            return Some(format!(
+                "adventofcode.com asked to wait, try again in {}s",
+                until - now
+            ));
+        }
+        None
+    }
+
+    /// Add an attempt and write the whole history back out
+    pub fn record(&mut self, attempt: Attempt) -> Result<(), Error> {
+        self.attempts.push(attempt);
+        let json = serde_json::to_string_pretty(&self.attempts)
+            .map_err(|e| Error::Config(format!("{}: {e}", self.path.display())))?;
+        std::fs::write(&self.path, json + "\n")?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Submitted {
+    /// The history says there's no point, nothing was posted
+    Refused(String),
+    Answered(Outcome),
+}
+
+/// Post `answer` for `day`/`part` unless `history` rules it out, recording whatever comes back
+pub fn submit(
+    config: &Config,
+    history: &mut History,
+    day: u8,
+    part: u8,
+    answer: &str,
+) -> Result<Submitted, Error> {
+    let now = unix_now();
+    if let Some(reason) = history.refusal(day, part, answer, now) {
+        return Ok(Submitted::Refused(reason));
+    }
+
+    let page = Client::new(config)?.submit(day, part, answer)?;
+    let outcome = parse_outcome(&page)?;
+    history.record(Attempt {
+        day,
+        part,
+        answer: answer.to_string(),
+        verdict: outcome.verdict,
+        time: now,
+        wait_until: outcome.wait.map(|wait| now + wait.as_secs()),
+    })?;
+    Ok(Submitted::Answered(outcome))
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::tests::stub_server;
+    use crate::tests::TempDir;
+
+    const RIGHT: &str = "<article><p>That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer to finding the Chief Historian.</p></article>";
+    const TOO_HIGH: &str = "<article><p>That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data. Please wait one minute before trying again. [<a href=\"/2024/day/1\">Return to Day 1</a>]</p></article>";
+    const TOO_RECENT: &str = "<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 12s left to wait. [<a href=\"/2024/day/1\">Return to Day 1</a>]</p></article>";
+
+    fn attempt(part: u8, answer: &str, verdict: Verdict) -> Attempt {
+        Attempt {
+            day: 1,
+            part,
+            answer: answer.to_string(),
+            verdict,
+            time: 1000,
+            wait_until: None,
+        }
+    }
+
+    #[test]
+    fn verdicts() {
+        let outcome = parse_outcome(RIGHT).unwrap();
+        assert_eq!((outcome.verdict, outcome.wait), (Verdict::Right, None));
+
+        let outcome = parse_outcome(TOO_HIGH).unwrap();
+        assert_eq!(outcome.verdict, Verdict::TooHigh);
+        assert_eq!(outcome.wait, Some(Duration::from_secs(60)));
+
+        let outcome = parse_outcome(TOO_RECENT).unwrap();
+        assert_eq!(outcome.verdict, Verdict::Wait);
+        assert_eq!(outcome.wait, Some(Duration::from_secs(72)));
+
+        let page = "That's not the right answer. Please wait 5 minutes before trying again.";
+        let outcome = parse_outcome(page).unwrap();
+        assert_eq!(outcome.verdict, Verdict::Wrong);
+        assert_eq!(outcome.wait, Some(Duration::from_secs(300)));
+
+        assert!(parse_outcome("You don't seem to be solving the right level.").is_err());
+    }
+
+    #[test]
+    fn refusals() {
+        let history = History {
+            path: PathBuf::new(),
+            attempts: vec![
+                attempt(1, "500", Verdict::TooHigh),
+                attempt(1, "100", Verdict::TooLow),
+                attempt(1, "abc", Verdict::Wrong),
+                attempt(2, "7", Verdict::Right),
+            ],
+        };
+        assert!(history.refusal(1, 1, "abc", 2000).is_some());
+        assert!(history.refusal(1, 1, "500", 2000).is_some());
+        assert!(history.refusal(1, 1, "501", 2000).is_some());
+        assert!(history.refusal(1, 1, "100", 2000).is_some());
+        assert!(history.refusal(1, 1, "-3", 2000).is_some());
+        assert_eq!(history.refusal(1, 1, "250", 2000), None);
+        assert_eq!(history.refusal(1, 1, "def", 2000), None);
+        assert!(
+            history
+                .refusal(1, 2, "8", 2000)
+                .unwrap()
+                .contains("already solved")
+        );
+        // Bounds only apply to the part and day they were found on
+        assert_eq!(history.refusal(2, 1, "500", 2000), None);
+    }
+
+    #[test]
+    fn waits() {
+        let dir = TempDir::new("waits");
+        let mut history = History::load(&dir.join("submissions.json")).unwrap();
+        history.attempts.push(Attempt {
+            wait_until: Some(1060),
+            ..attempt(1, "5", Verdict::Wrong)
+        });
+        assert!(history.refusal(1, 1, "6", 1030).unwrap().contains("30s"));
+        assert_eq!(history.refusal(1, 1, "6", 1060), None);
+        assert_eq!(history.refusal(2, 1, "6", 1030), None);
+    }
+
+    #[test]
+    fn submits_and_records() {
+        let (config, server) = stub_server(vec![(200, TOO_HIGH)]);
+        let dir = TempDir::new("submit");
+        let mut history = History::load(&dir.join("submissions.json")).unwrap();
+
+        let submitted = submit(&config, &mut history, 1, 1, "500").unwrap();
+        let Submitted::Answered(outcome) = submitted else {
+            panic!("expected an answer, got {submitted:?}");
+        };
+        assert_eq!(outcome.verdict, Verdict::TooHigh);
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("POST /2024/day/1/answer "));
+        assert!(requests[0].ends_with("level=1&answer=500"));
+
+        // Written to disk, and the stub is gone so a second post would fail
+        let reloaded = History::load(&history.path).unwrap();
+        assert_eq!(reloaded.attempts()[0].verdict, Verdict::TooHigh);
+        assert!(reloaded.attempts()[0].wait_until.is_some());
+        let again = submit(&config, &mut history, 1, 1, "600").unwrap();
+        assert!(matches!(again, Submitted::Refused(_)));
+    }
+}
@@ -0,0 +1,97 @@
+/*
+ * A letter grid as an SVG, with lines drawn through the letters, e.g. the words found in a word
+ * search. Each cell is a `CELL` pixel square and a line runs between the centres of its end cells.
+ */
+use std::fmt::Write;
+
+use crate::geometry::Point;
+use crate::grid::Grid;
+
+const CELL: i32 = 24;
+
+/// A line from the middle of one cell to the middle of another, in any SVG colour
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Line {
+    pub from: Point,
+    pub to: Point,
+    pub colour: &'static str,
+}
+
+/// `grid` as rows of letters with `lines` drawn over them, half see-through so the letters show
+pub fn letters(grid: &Grid<char>, lines: &[Line]) -> String {
+    let (width, height) = (grid.width() as i32 * CELL, grid.height() as i32 * CELL);
+    let mut out = String::new();
+    writeln!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+    )
+    .unwrap();
+    writeln!(out, r##"<rect width="100%" height="100%" fill="#fff"/>"##).unwrap();
+
+    writeln!(
+        out,
+        r#"<g font-family="monospace" font-size="{}" text-anchor="middle" dominant-baseline="central">"#,
+        CELL * 2 / 3
+    )
+    .unwrap();
+    for (p, &c) in grid.iter() {
+        let (x, y) = centre(p);
+        writeln!(out, r#"<text x="{x}" y="{y}">{}</text>"#, escape(c)).unwrap();
+    }
+    out += "</g>\n";
+
+    writeln!(
+        out,
+        r#"<g stroke-width="{}" stroke-linecap="round" stroke-opacity="0.5">"#,
+        CELL / 2
+    )
+    .unwrap();
+    for line in lines {
+        let ((x1, y1), (x2, y2)) = (centre(line.from), centre(line.to));
+        writeln!(
+            out,
+            r#"<line x1="{x1}" y1="{y1}" x2="{x2}" y2="{y2}" stroke="{}"/>"#,
+            line.colour
+        )
+        .unwrap();
+    }
+    out += "</g>\n</svg>\n";
+    out
+}
+
+// SVG x and y of the middle of a cell. Grid `x` is the row, so it's SVG `y`.
+fn centre(p: Point) -> (i32, i32) {
+    (p.y * CELL + CELL / 2, p.x * CELL + CELL / 2)
+}
+
+fn escape(c: char) -> String {
+    match c {
+        '<' => "&lt;".to_string(),
+        '>' => "&gt;".to_string(),
+        '&' => "&amp;".to_string(),
+        c => c.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn draws_letters_and_lines() {
+        let grid = Grid::parse("ab\n<d\n", Some).unwrap();
+        let line = Line {
+            from: Point::new(0, 0),
+            to: Point::new(1, 1),
+            colour: "red",
+        };
+        let svg = letters(&grid, &[line]);
+        assert!(
+            svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="48" height="48""#)
+        );
+        assert!(svg.contains(r#"<text x="36" y="12">b</text>"#));
+        assert!(svg.contains(r#"<text x="12" y="36">&lt;</text>"#));
+        assert!(svg.contains(r#"<line x1="12" y1="12" x2="36" y2="36" stroke="red"/>"#));
+        assert!(svg.ends_with("</svg>\n"));
+    }
+}
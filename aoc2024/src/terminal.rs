@@ -0,0 +1,175 @@
+/*
+ * Animating a grid in the terminal: a player that shows numbered frames one after another with
+ * keys to pause and step, and a helper to draw the part of a grid that fits on screen.
+ *
+ *  space       play / pause
+ *  → or n      next frame (pauses)
+ *  ← or p      previous frame (pauses)
+ *  + / -       twice / half as fast
+ *  q or Esc    quit
+ */
+use std::io::{self, Write};
+use std::num::NonZeroU32;
+use std::time::{Duration, Instant};
+
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::style::StyledContent;
+use crossterm::{cursor, queue, terminal};
+
+use crate::geometry::Point;
+use crate::grid::Grid;
+
+/// Show frames `0..frames`, drawing each with `draw(frame, rows, columns)` to fit in that much
+/// of the screen, starting at `fps` frames a second. Returns when the user quits; the last frame
+/// stays up until then.
+pub fn play(
+    frames: usize,
+    fps: NonZeroU32,
+    mut draw: impl FnMut(usize, usize, usize) -> String,
+) -> io::Result<()> {
+    let _screen = Screen::enter()?;
+    let mut out = io::stdout();
+    let last = frames.saturating_sub(1);
+    let mut frame = 0;
+    let mut fps = fps.get();
+    let mut playing = true;
+
+    loop {
+        let (columns, rows) = terminal::size()?;
+        let (columns, rows) = (columns as usize, (rows as usize).saturating_sub(1));
+        queue!(
+            out,
+            cursor::MoveTo(0, 0),
+            terminal::Clear(terminal::ClearType::All)
+        )?;
+        // Raw mode doesn't turn `\n` into a new line on its own
+        write!(
+            out,
+            "{}\r\n",
+            draw(frame, rows, columns).replace('\n', "\r\n")
+        )?;
+        let state = if playing { "playing" } else { "paused" };
+        write!(
+            out,
+            "{}/{frames} {state} at {fps} fps | space: play/pause  ←/→: step  +/-: speed  q: quit",
+            frame + 1
+        )?;
+        out.flush()?;
+
+        // Wait out the frame, or indefinitely when paused, unless a key comes first
+        let deadline = Instant::now() + Duration::from_secs(1) / fps;
+        loop {
+            let wait = if playing {
+                deadline.saturating_duration_since(Instant::now())
+            } else {
+                Duration::from_secs(60)
+            };
+            if !event::poll(wait)? {
+                if playing {
+                    frame = (frame + 1).min(last);
+                    playing = frame < last;
+                }
+                break;
+            }
+            match event::read()? {
+                Event::Key(key) if key.kind == KeyEventKind::Press => match key.code {
+                    KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                    KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        return Ok(());
+                    }
+                    KeyCode::Char(' ') => {
+                        // Playing from the end starts again
+                        if !playing && frame == last {
+                            frame = 0;
+                        }
+                        playing = !playing;
+                    }
+                    KeyCode::Right | KeyCode::Char('n') => {
+                        playing = false;
+                        frame = (frame + 1).min(last);
+                    }
+                    KeyCode::Left | KeyCode::Char('p') => {
+                        playing = false;
+                        frame = frame.saturating_sub(1);
+                    }
+                    KeyCode::Char('+') | KeyCode::Char('=') => fps = (fps * 2).min(1000),
+                    KeyCode::Char('-') => fps = (fps / 2).max(1),
+                    _ => continue,
+                },
+                Event::Resize(..) => {}
+                _ => continue,
+            }
+            break;
+        }
+    }
+}
+
+/// The `rows` by `columns` window of `grid` that keeps `focus` in view (in the middle where the
+/// grid allows), one line per row, with each cell styled by `cell`
+pub fn window<T>(
+    grid: &Grid<T>,
+    focus: Point,
+    rows: usize,
+    columns: usize,
+    cell: impl Fn(Point, &T) -> StyledContent<char>,
+) -> String {
+    let (x0, height) = span(focus.x, rows, grid.height());
+    let (y0, width) = span(focus.y, columns, grid.width());
+
+    let mut out = String::with_capacity(height * (width + 1));
+    for x in x0..x0 + height {
+        for y in y0..y0 + width {
+            let p = Point::new(x as i32, y as i32);
+            out += &cell(p, grid.get(p).unwrap()).to_string();
+        }
+        out.push('\n');
+    }
+    out
+}
+
+// The first index and length of a `view` long stretch of `0..len` centred on `focus` if possible
+fn span(focus: i32, view: usize, len: usize) -> (usize, usize) {
+    let view = view.min(len);
+    let start = (focus.max(0) as usize).saturating_sub(view / 2);
+    (start.min(len - view), view)
+}
+
+// Raw mode on the alternate screen, put back however we leave
+struct Screen;
+
+impl Screen {
+    fn enter() -> io::Result<Screen> {
+        terminal::enable_raw_mode()?;
+        let screen = Screen;
+        crossterm::execute!(io::stdout(), terminal::EnterAlternateScreen, cursor::Hide)?;
+        Ok(screen)
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        let _ = crossterm::execute!(io::stdout(), cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::style::style;
+
+    use super::*;
+
+    #[test]
+    fn follows_the_focus() {
+        let grid = Grid::parse("abcd\nefgh\nijkl\n", Some).unwrap();
+        let plain = |_, &c: &char| style(c);
+        assert_eq!(window(&grid, Point::new(0, 0), 2, 2, plain), "ab\nef\n");
+        assert_eq!(window(&grid, Point::new(2, 2), 2, 2, plain), "fg\njk\n");
+        assert_eq!(window(&grid, Point::new(2, 3), 2, 3, plain), "fgh\njkl\n");
+        // Bigger than the grid is the whole grid
+        assert_eq!(
+            window(&grid, Point::new(1, 1), 10, 10, plain),
+            "abcd\nefgh\nijkl\n"
+        );
+    }
+}
@@ -0,0 +1,259 @@
+/*
+ * Word search: find every occurrence of a list of words in a grid of letters, reading in any
+ * of a given set of directions.
+ *
+ * The words go into an Aho-Corasick automaton (a trie where each node also knows the longest
+ * suffix of its text that's a prefix of some word), so each line of the grid is read once per
+ * direction whatever the number of words, and overlapping words are all found.
+ */
+use std::collections::VecDeque;
+
+use crate::geometry::{Direction, Point};
+use crate::grid::Grid;
+
+/// `length` cells from `start`, reading in `direction`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Match {
+    pub start: Point,
+    pub direction: Direction,
+    pub length: usize,
+}
+
+impl Match {
+    /// Where the last letter is
+    pub fn end(&self) -> Point {
+        self.start + self.direction.vector() * (self.length as i32 - 1)
+    }
+
+    /// Every letter's cell, in reading order
+    pub fn cells(&self) -> impl Iterator<Item = Point> + use<> {
+        let (start, step) = (self.start, self.direction.vector());
+        (0..self.length as i32).map(move |i| start + step * i)
+    }
+}
+
+/// One word found in the grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Found {
+    pub location: Match,
+    /// Index into [`WordSearch::words`]
+    pub word: usize,
+}
+
+/// The words to look for, built once and used on any number of grids
+#[derive(Debug, Clone)]
+pub struct WordSearch {
+    words: Vec<String>,
+    nodes: Vec<Node>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Node {
+    children: Vec<(char, usize)>,
+    // Where to carry on from when the next letter has no child here
+    fail: usize,
+    // Words ending here, including those that are a suffix of this node's text
+    output: Vec<usize>,
+}
+
+const ROOT: usize = 0;
+
+impl WordSearch {
+    /// Panics on an empty word, which would match everywhere
+    pub fn new<S: AsRef<str>>(words: impl IntoIterator<Item = S>) -> Self {
+        let words: Vec<String> = words.into_iter().map(|w| w.as_ref().to_string()).collect();
+        let mut nodes = vec![Node::default()];
+        for (i, word) in words.iter().enumerate() {
+            assert!(!word.is_empty(), "can't search for an empty word");
+            let mut node = ROOT;
+            for c in word.chars() {
+                node = match child(&nodes, node, c) {
+                    Some(next) => next,
+                    None => {
+                        nodes.push(Node::default());
+                        let next = nodes.len() - 1;
+                        nodes[node].children.push((c, next));
+                        next
+                    }
+                };
+            }
+            nodes[node].output.push(i);
+        }
+
+        // Breadth first, so every node's fail link is finished before its children need it
+        let mut queue: VecDeque<usize> = nodes[ROOT].children.iter().map(|&(_, n)| n).collect();
+        while let Some(node) = queue.pop_front() {
+            for (c, next) in nodes[node].children.clone() {
+                let mut fail = nodes[node].fail;
+                let fail = loop {
+                    if let Some(to) = child(&nodes, fail, c) {
+                        break to;
+                    }
+                    if fail == ROOT {
+                        break ROOT;
+                    }
+                    fail = nodes[fail].fail;
+                };
+                nodes[next].fail = fail;
+                let inherited = nodes[fail].output.clone();
+                nodes[next].output.extend(inherited);
+                queue.push_back(next);
+            }
+        }
+        WordSearch { words, nodes }
+    }
+
+    pub fn words(&self) -> &[String] {
+        &self.words
+    }
+
+    /// Every occurrence of every word reading in any of `directions`, ordered by where it starts,
+    /// then direction, then length. A word that reads the same backwards is found once each way.
+    pub fn find(&self, grid: &Grid<char>, directions: &[Direction]) -> Vec<Found> {
+        let mut found = vec![];
+        for &direction in directions {
+            // Each line in this direction starts where a step back would leave the grid
+            let starts = grid
+                .points()
+                .filter(|&p| !grid.contains(p + direction.reverse()));
+            for start in starts {
+                let mut node = ROOT;
+                for (p, &c) in grid.ray(start, direction.vector()) {
+                    node = self.next(node, c);
+                    for &word in &self.nodes[node].output {
+                        let length = self.words[word].chars().count();
+                        let start = p + direction.reverse().vector() * (length as i32 - 1);
+                        found.push(Found {
+                            location: Match {
+                                start,
+                                direction,
+                                length,
+                            },
+                            word,
+                        });
+                    }
+                }
+            }
+        }
+        found.sort();
+        found
+    }
+
+    // Follow `c` from `node`, falling back along the fail links until something matches
+    fn next(&self, mut node: usize, c: char) -> usize {
+        loop {
+            if let Some(next) = child(&self.nodes, node, c) {
+                return next;
+            }
+            if node == ROOT {
+                return ROOT;
+            }
+            node = self.nodes[node].fail;
+        }
+    }
+}
+
+fn child(nodes: &[Node], node: usize, c: char) -> Option<usize> {
+    nodes[node]
+        .children
+        .iter()
+        .find(|&&(letter, _)| letter == c)
+        .map(|&(_, next)| next)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::random;
+
+    // Try every word from every cell in every direction
+    fn brute_force(words: &[&str], grid: &Grid<char>, directions: &[Direction]) -> Vec<Found> {
+        let mut found = vec![];
+        for start in grid.points() {
+            for &direction in directions {
+                for (word, text) in words.iter().enumerate() {
+                    let length = text.chars().count();
+                    let read = grid
+                        .ray(start, direction.vector())
+                        .map(|(_, c)| *c)
+                        .take(length);
+                    if read.eq(text.chars()) {
+                        let location = Match {
+                            start,
+                            direction,
+                            length,
+                        };
+                        found.push(Found { location, word });
+                    }
+                }
+            }
+        }
+        found.sort();
+        found
+    }
+
+    #[test]
+    fn overlapping_words() {
+        let grid = Grid::parse("ABCD\n", Some).unwrap();
+        let search = WordSearch::new(["ABC", "BC", "B", "CD", "DCB", "E"]);
+        let found: Vec<_> = search
+            .find(&grid, &[Direction::East, Direction::West])
+            .into_iter()
+            .map(|f| {
+                (
+                    search.words()[f.word].as_str(),
+                    f.location.start.y,
+                    f.location.direction,
+                )
+            })
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("ABC", 0, Direction::East),
+                ("B", 1, Direction::East),
+                ("BC", 1, Direction::East),
+                ("B", 1, Direction::West),
+                ("CD", 2, Direction::East),
+                ("DCB", 3, Direction::West),
+            ]
+        );
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let mut next = random();
+        let letters: String = (0..12 * 15)
+            .map(|i| {
+                let c = ['X', 'M', 'A', 'S'][(next() % 4) as usize];
+                if i % 15 == 14 { '\n' } else { c }
+            })
+            .collect();
+        let grid = Grid::parse(&letters, Some).unwrap();
+
+        let words = ["XMAS", "MAS", "SAM", "AMAS", "XX", "S"];
+        let search = WordSearch::new(words);
+        for directions in [
+            &Direction::ALL[..],
+            &Direction::DIAGONAL,
+            &[Direction::South],
+        ] {
+            let found = search.find(&grid, directions);
+            assert_eq!(found, brute_force(&words, &grid, directions));
+        }
+    }
+
+    #[test]
+    fn match_cells() {
+        let m = Match {
+            start: Point::new(3, 0),
+            direction: Direction::NorthEast,
+            length: 3,
+        };
+        assert_eq!(m.end(), Point::new(1, 2));
+        assert_eq!(
+            m.cells().collect::<Vec<_>>(),
+            [Point::new(3, 0), Point::new(2, 1), Point::new(1, 2)]
+        );
+    }
+}